@@ -0,0 +1,418 @@
+use std::time::Duration;
+
+use config::MergeMethod;
+use forge::{BranchMerge, CheckState, Forge, PullRequest, Repository};
+use log::{debug, error, info, warn};
+use tokio::time::{sleep, Instant};
+
+use crate::plan::Action;
+use crate::summary::{Outcome, RepoSummary, Status};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// how long a head without any check is given for its checks to show up,
+/// the forge creating them a while after the push
+const CHECKS_GRACE: Duration = Duration::from_secs(60);
+/// how many times a pull request is fetched for its mergeability to be known
+const MERGEABLE_POLLS: u32 = 5;
+const MERGEABLE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+async fn wait_for_checks(
+    forge: &dyn Forge,
+    repo: &Repository,
+    pr: &PullRequest,
+    deadline: Instant,
+) -> bool {
+    info!("Waiting for checks on #{} for {}", pr.number, repo.name);
+    let grace = Instant::now() + CHECKS_GRACE;
+    loop {
+        match forge.list_checks(repo, &pr.head_sha).await {
+            Ok(checks) if checks.is_empty() && Instant::now() < grace => {
+                debug!("No checks yet on #{}", pr.number);
+            }
+            Ok(checks) => {
+                if checks
+                    .iter()
+                    .all(|check| check.state != CheckState::Pending)
+                {
+                    let failed: Vec<&str> = checks
+                        .iter()
+                        .filter(|check| check.state == CheckState::Failure)
+                        .map(|check| check.name.as_str())
+                        .collect();
+                    if !failed.is_empty() {
+                        warn!("Checks failed on #{}: {}", pr.number, failed.join(", "));
+                        return false;
+                    }
+                    return true;
+                }
+            }
+            Err(e) => {
+                error!(
+                    "Unable to get checks for #{}: {}",
+                    pr.number,
+                    e.error_message()
+                );
+                return false;
+            }
+        }
+
+        if Instant::now() >= deadline {
+            warn!("Timed out waiting for checks on #{}", pr.number);
+            return false;
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn update_pull_request(
+    forge: &dyn Forge,
+    repo: &Repository,
+    pr: &PullRequest,
+    checks_timeout: Duration,
+) -> Option<PullRequest> {
+    info!("Updating {} with {} for {}", pr.head, pr.base, repo.name);
+    match forge.update_pull(repo, pr).await {
+        Ok(_) => debug!("#{} update requested", pr.number),
+        Err(e) => {
+            error!(
+                "Failed to update #{} branch: {}",
+                pr.number,
+                e.error_message()
+            );
+            return None;
+        }
+    }
+
+    let deadline = Instant::now() + checks_timeout;
+
+    // the update happens asynchronously, wait for the new head commit
+    let updated_pr = loop {
+        if Instant::now() >= deadline {
+            warn!("Timed out waiting for #{} to be updated", pr.number);
+            return None;
+        }
+        sleep(POLL_INTERVAL).await;
+        match forge.get_pull(repo, pr.number).await {
+            Ok(updated_pr) if updated_pr.head_sha != pr.head_sha => break updated_pr,
+            Ok(_) => continue,
+            Err(e) => {
+                error!("Unable to get pull {:?}", e.error_message());
+                return None;
+            }
+        }
+    };
+
+    if !wait_for_checks(forge, repo, &updated_pr, deadline).await {
+        return None;
+    }
+
+    // mergeability is recomputed after the push
+    loop {
+        match forge.get_pull(repo, pr.number).await {
+            Ok(updated_pr) if updated_pr.mergeable.is_some() => return Some(updated_pr),
+            Ok(_) => {}
+            Err(e) => {
+                error!("Unable to get pull {:?}", e.error_message());
+                return None;
+            }
+        }
+        if Instant::now() >= deadline {
+            warn!("Timed out waiting for #{} to be mergeable", pr.number);
+            return None;
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// `pr` once the forge knows whether it is mergeable, which it computes in
+/// the background after the pull request is opened; `pr` as is when it
+/// doesn't tell in time.
+async fn with_mergeability(
+    forge: &dyn Forge,
+    repo: &Repository,
+    mut pr: PullRequest,
+) -> PullRequest {
+    let mut polls = 0;
+    while pr.mergeable.is_none() && polls < MERGEABLE_POLLS {
+        if polls > 0 {
+            sleep(MERGEABLE_POLL_INTERVAL).await;
+        }
+        polls += 1;
+        match forge.get_pull(repo, pr.number).await {
+            Ok(fetched) => pr = fetched,
+            Err(e) => {
+                error!("Unable to get pull #{}: {}", pr.number, e.error_message());
+                break;
+            }
+        }
+    }
+    pr
+}
+
+fn forge_method(method: Option<MergeMethod>) -> forge::MergeMethod {
+    match method.unwrap_or_default() {
+        MergeMethod::Merge => forge::MergeMethod::Merge,
+        MergeMethod::Squash => forge::MergeMethod::Squash,
+        MergeMethod::Rebase => forge::MergeMethod::Rebase,
+    }
+}
+
+/// The pull request `number` refers to: the one at hand when it matches or
+/// is `None`, fetched otherwise.
+async fn pull_request(
+    forge: &dyn Forge,
+    repo: &Repository,
+    number: Option<u64>,
+    pull: Option<PullRequest>,
+) -> Result<PullRequest, Outcome> {
+    match (number, pull) {
+        (None, Some(pr)) => Ok(pr),
+        (Some(number), Some(pr)) if pr.number == number => Ok(pr),
+        (Some(number), _) => forge.get_pull(repo, number).await.map_err(|e| {
+            error!("Unable to get pull #{}: {}", number, e.error_message());
+            Outcome::failed(format!("unable to get #{}: {}", number, e.error_message()))
+        }),
+        (None, None) => Err(Outcome::failed("no pull request to merge")),
+    }
+}
+
+/// Releases `tag` from `branch` unless it is released already.
+async fn tag_release(
+    forge: &dyn Forge,
+    repo: &Repository,
+    tag: &str,
+    branch: &str,
+) -> Result<(), String> {
+    let released = forge
+        .find_release(repo, tag)
+        .await
+        .map_err(|e| e.error_message())?;
+    if released.is_some() {
+        info!("`{}` is already released for {}", tag, repo.name);
+        return Ok(());
+    }
+    let release = forge
+        .create_release(repo, tag, branch, tag, false)
+        .await
+        .map_err(|e| e.error_message())?;
+    info!("Released `{}` for {}: {}", tag, repo.name, release.url);
+    Ok(())
+}
+
+/// Makes the writes of a plan in order, stopping at the first one that
+/// fails. `pull` is the pull request the plan was made from, if any.
+pub async fn apply_actions(
+    forge: &dyn Forge,
+    repo: &Repository,
+    actions: &[Action],
+    checks_timeout: Duration,
+    mut pull: Option<PullRequest>,
+    summary: &mut RepoSummary,
+) -> (Outcome, Option<PullRequest>) {
+    let mut outcome = Outcome::new(Status::NothingToDo, "");
+    for action in actions {
+        debug!("{}: {}", repo.name, action);
+        match action {
+            Action::CreateBranch { branch, from } => {
+                match forge.create_branch(repo, branch, from).await {
+                    Ok(_) => {
+                        info!("Branch `{}` created successfully on {}", branch, repo.name);
+                        outcome = Outcome::done(format!("created `{}` from `{}`", branch, from));
+                    }
+                    Err(e) => {
+                        error!(
+                            "Error on creating branch `{}` for `{}`: {}",
+                            branch,
+                            repo.name,
+                            e.error_message()
+                        );
+                        let failure = Outcome::failed(format!(
+                            "unable to create `{}`: {}",
+                            branch,
+                            e.error_message()
+                        ));
+                        return (failure, pull);
+                    }
+                }
+            }
+            Action::OpenPull {
+                from,
+                to,
+                title,
+                body,
+                reviewers,
+            } => {
+                info!(
+                    "Creating pull request from {} into {} for {}",
+                    from, to, repo.name
+                );
+                match forge
+                    .create_pull(repo, from, to, title, body.as_deref())
+                    .await
+                {
+                    Ok(pr) => {
+                        info!("Pull request opened for {}: {}", repo.name, pr.url);
+                        outcome = Outcome::done(format!("#{} opened: {}", pr.number, pr.url));
+                        // unrequested reviews don't undo the pull request
+                        if !reviewers.is_empty() {
+                            if let Err(e) = forge.request_reviewers(repo, &pr, reviewers).await {
+                                error!(
+                                    "Unable to request reviews on #{}: {}",
+                                    pr.number,
+                                    e.error_message()
+                                );
+                                summary.errors.push(format!(
+                                    "unable to request reviewers: {}",
+                                    e.error_message()
+                                ));
+                            }
+                        }
+                        pull = Some(pr);
+                    }
+                    Err(e) => {
+                        error!("Unable to create a new PR {}", e.error_message());
+                        let failure = Outcome::failed(format!(
+                            "unable to create a pull request: {}",
+                            e.error_message()
+                        ));
+                        return (failure, pull);
+                    }
+                }
+            }
+            Action::UpdatePull { number } => {
+                let pr = match pull_request(forge, repo, Some(*number), pull.take()).await {
+                    Ok(pr) => pr,
+                    Err(failure) => return (failure, None),
+                };
+                match update_pull_request(forge, repo, &pr, checks_timeout).await {
+                    Some(updated_pr) => pull = Some(updated_pr),
+                    None => {
+                        let failure = Outcome::failed(format!(
+                            "#{} could not be brought up to date",
+                            pr.number
+                        ));
+                        return (failure, Some(pr));
+                    }
+                }
+            }
+            Action::MergePull { number, method } => {
+                let pr = match pull_request(forge, repo, *number, pull.take()).await {
+                    Ok(pr) => pr,
+                    Err(failure) => return (failure, None),
+                };
+                let pr = with_mergeability(forge, repo, pr).await;
+                info!("Merging {} into {} for {}", pr.head, pr.base, repo.name);
+                debug!("is mergeable ? {:?}", pr.mergeable.unwrap_or(false));
+                match pr.mergeable {
+                    Some(true) => {}
+                    Some(false) => {
+                        warn!("Pull request is not mergeable, there is a conflict");
+                        let conflict =
+                            Outcome::new(Status::Conflict, format!("#{} has conflicts", pr.number));
+                        return (conflict, Some(pr));
+                    }
+                    None => {
+                        info!("Unable to know if it's mergeable, please try later");
+                        let pending = Outcome::new(
+                            Status::Pending,
+                            format!("#{} mergeability unknown, try later", pr.number),
+                        );
+                        return (pending, Some(pr));
+                    }
+                }
+                match forge.merge_pull(repo, &pr, forge_method(*method)).await {
+                    Ok(merge_status) if merge_status.merged => {
+                        summary.merged_sha = merge_status.sha;
+                        outcome = Outcome::done(format!("merged #{}", pr.number));
+                    }
+                    Ok(_) => {
+                        let failure = Outcome::failed(format!("#{} was not merged", pr.number));
+                        return (failure, Some(pr));
+                    }
+                    Err(e) => {
+                        error!("Failed to merge #{}, {}", pr.number, e.error_message());
+                        if let Some(extra_info) = e.extra_info() {
+                            debug!("original response: {:?}", extra_info);
+                        }
+                        let failure = Outcome::failed(format!(
+                            "#{} merge failed: {}",
+                            pr.number,
+                            e.error_message()
+                        ));
+                        return (failure, Some(pr));
+                    }
+                }
+                pull = Some(pr);
+            }
+            Action::MergeBranches { from, to, message } => {
+                info!(
+                    "Merging {} into {} for {} without pull request",
+                    from, to, repo.name
+                );
+                match forge.merge_branches(repo, to, from, message).await {
+                    Ok(BranchMerge::Merged(sha)) => {
+                        info!("Merged {} into {}: {}", from, to, sha);
+                        summary.merged_sha = Some(sha);
+                        outcome = Outcome::done(format!("merged {} into {}", from, to));
+                    }
+                    Ok(BranchMerge::NothingToMerge) => {
+                        info!("Nothing to merge !");
+                        let nothing = Outcome::new(Status::NothingToDo, "nothing to merge");
+                        return (nothing, pull);
+                    }
+                    Ok(BranchMerge::Conflict) => {
+                        warn!(
+                            "Unable to merge {} into {} for {}, there is a conflict",
+                            from, to, repo.name
+                        );
+                        return (Outcome::new(Status::Conflict, "merge conflict"), pull);
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to merge {} into {} for {}: {}",
+                            from,
+                            to,
+                            repo.name,
+                            e.error_message()
+                        );
+                        if let Some(extra_info) = e.extra_info() {
+                            debug!("original response: {:?}", extra_info);
+                        }
+                        let failure =
+                            Outcome::failed(format!("merge failed: {}", e.error_message()));
+                        return (failure, pull);
+                    }
+                }
+            }
+            // a branch left behind doesn't undo the promotion
+            Action::DeleteBranch { branch } => match forge.delete_branch(repo, branch).await {
+                Ok(_) => summary.branch_deleted = true,
+                Err(e) => {
+                    error!(
+                        "Failed to delete branch {}. reason: {}",
+                        branch,
+                        e.error_message()
+                    );
+                    if let Some(extra_info) = e.extra_info() {
+                        debug!("original response: {:?}", extra_info)
+                    }
+                    summary.errors.push(format!(
+                        "unable to delete `{}`: {}",
+                        branch,
+                        e.error_message()
+                    ));
+                }
+            },
+            // an untagged stage doesn't undo the promotion either
+            Action::TagRelease { tag, branch } => {
+                if let Err(message) = tag_release(forge, repo, tag, branch).await {
+                    error!("Unable to release `{}` for {}: {}", tag, repo.name, message);
+                    summary
+                        .errors
+                        .push(format!("unable to release `{}`: {}", tag, message));
+                }
+            }
+        }
+    }
+    (outcome, pull)
+}
@@ -0,0 +1,516 @@
+use std::time::Duration;
+
+use clap::{Args, Subcommand};
+use config::{Config, MergeMethod, RepoConfig};
+use forge::{CompareStatus, Forge, PullRequest, Repository};
+use log::{error, info, warn};
+
+use crate::apply::apply_actions;
+use crate::init::InitArgs;
+use crate::plan::Action;
+use crate::plan_merge;
+use crate::summary::{Outcome, RepoSummary, Status};
+
+/// The branches of a promotion, each defaulting to the repo's default branch.
+/// Logical names like `@prod` are looked up in the config.
+#[derive(Args, Debug, Clone)]
+pub struct BranchPair {
+    #[clap(long = "from", value_parser)]
+    /// source branch, defaults to the repo's default branch
+    pub source: Option<String>,
+    #[clap(long = "to", value_parser)]
+    /// destination branch, defaults to the repo's default branch
+    pub destination: Option<String>,
+}
+
+impl BranchPair {
+    /// `from` and `to` for `repo`.
+    pub fn resolve(
+        &self,
+        repo: &Repository,
+        config: &Config,
+        repo_config: &RepoConfig,
+    ) -> Result<(String, String), String> {
+        let resolve = |branch: &Option<String>, flag: &str| match branch {
+            Some(branch) => config.branch_of(repo_config, branch),
+            None => repo
+                .default_branch
+                .clone()
+                .ok_or_else(|| format!("no {flag} given and no default branch")),
+        };
+        let from = resolve(&self.source, "--from")?;
+        let to = resolve(&self.destination, "--to")?;
+        if from == to {
+            return Err(format!("source and destination are both `{from}`"));
+        }
+        Ok((from, to))
+    }
+}
+
+// commands run on each repo of the config, reported like a promotion; not a
+// doc comment, clap would make it the about of the whole cli
+#[derive(Subcommand, Debug, Clone)]
+pub enum RepoCommand {
+    /// create or delete a branch
+    #[clap(subcommand)]
+    Branch(BranchCommand),
+    /// open, list, close or merge the pull request of `from` into `to`
+    #[clap(subcommand)]
+    Pr(PrCommand),
+    /// tag a branch and publish a release
+    Release(ReleaseArgs),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum BranchCommand {
+    /// create `branch` unless it exists
+    Create {
+        #[clap(value_parser)]
+        branch: String,
+        #[clap(long, value_parser)]
+        /// branch to start from, defaults to the repo's default branch
+        from: Option<String>,
+    },
+    /// delete `branch` if it exists, never the default branch
+    Delete {
+        #[clap(value_parser)]
+        branch: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum PrCommand {
+    /// open the pull request unless there is one or nothing to merge
+    Open(PrOpenArgs),
+    /// print the open pull request
+    List(BranchPair),
+    /// close the open pull request without merging it
+    Close(BranchPair),
+    /// merge the open pull request
+    Merge(PrMergeArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PrOpenArgs {
+    #[clap(flatten)]
+    pub branches: BranchPair,
+    #[clap(long, value_parser, required_unless_present = "title")]
+    /// reference branch: org/project#issue_number
+    pub reference: Option<String>,
+    #[clap(long, value_parser)]
+    /// title of the pull request, defaults to one naming the reference
+    pub title: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PrMergeArgs {
+    #[clap(flatten)]
+    pub branches: BranchPair,
+    #[clap(long, value_parser)]
+    /// delete the source branch after merge
+    pub delete_branches: bool,
+    #[clap(long, value_parser)]
+    /// update pull requests that are behind their base branch, wait for checks, then merge
+    pub update_branches: bool,
+    #[clap(long, value_parser, default_value_t = 600)]
+    /// seconds to wait for an updated pull request to be ready to merge
+    pub checks_timeout: u64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ReleaseArgs {
+    #[clap(value_parser)]
+    /// tag of the release, created if it doesn't exist
+    pub tag: String,
+    #[clap(long, value_parser)]
+    /// branch to tag, defaults to the repo's default branch
+    pub branch: Option<String>,
+    #[clap(long, value_parser)]
+    /// name of the release, defaults to the tag
+    pub name: Option<String>,
+    #[clap(long, value_parser)]
+    /// save the release as a draft instead of publishing it
+    pub draft: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// print the config as it is read, tokens redacted
+    Show,
+    /// print the JSON Schema config files are validated against
+    Schema,
+    /// write a config for repos picked among an owner's, to `--config` or
+    /// `./config.json`
+    Init(InitArgs),
+}
+
+pub async fn run(
+    forge: &dyn Forge,
+    repo: &Repository,
+    config: &Config,
+    repo_config: &RepoConfig,
+    command: &RepoCommand,
+    summary: &mut RepoSummary,
+) -> Outcome {
+    let branch_of = |branch: &String| {
+        config.branch_of(repo_config, branch).map_err(|e| {
+            error!("Skipping {}: {}", repo.name, e);
+            Outcome::new(Status::Skipped, e)
+        })
+    };
+    let overrides = repo_config.overrides().cloned().unwrap_or_default();
+    match command {
+        RepoCommand::Branch(BranchCommand::Create { branch, from }) => {
+            let branches = branch_of(branch).and_then(|branch| {
+                let from = from.as_ref().map(branch_of).transpose()?;
+                Ok((branch, from))
+            });
+            match branches {
+                Ok((branch, from)) => create_branch(forge, repo, &branch, from.as_ref()).await,
+                Err(skipped) => skipped,
+            }
+        }
+        RepoCommand::Branch(BranchCommand::Delete { branch }) => match branch_of(branch) {
+            Ok(branch) => delete_branch(forge, repo, &branch, summary).await,
+            Err(skipped) => skipped,
+        },
+        RepoCommand::Pr(command) => {
+            let branches = match command {
+                PrCommand::Open(args) => &args.branches,
+                PrCommand::List(branches) | PrCommand::Close(branches) => branches,
+                PrCommand::Merge(args) => &args.branches,
+            };
+            let (from, to) = match branches.resolve(repo, config, repo_config) {
+                Ok(branches) => branches,
+                Err(e) => {
+                    error!("Skipping {}: {}", repo.name, e);
+                    return Outcome::new(Status::Skipped, e);
+                }
+            };
+            let pull = match command {
+                PrCommand::Open(args) => {
+                    let reviewers = overrides.reviewers;
+                    open_pull(forge, repo, &from, &to, args, reviewers, summary).await
+                }
+                PrCommand::List(_) => list_pull(forge, repo, &from, &to).await,
+                PrCommand::Close(_) => close_pull(forge, repo, &from, &to).await,
+                PrCommand::Merge(args) => {
+                    let method = overrides.merge_method;
+                    merge_pull(forge, repo, &from, &to, args, method, summary).await
+                }
+            };
+            let (outcome, pull_request) = match pull {
+                Ok(pull) => pull,
+                Err(failure) => (failure, None),
+            };
+            if let Some(pr) = pull_request {
+                summary.pull_number = Some(pr.number);
+                summary.pull_url = Some(pr.url);
+            }
+            outcome
+        }
+        RepoCommand::Release(args) => match args.branch.as_ref().map(branch_of).transpose() {
+            Ok(branch) => release(forge, repo, branch.as_ref(), args).await,
+            Err(skipped) => skipped,
+        },
+    }
+}
+
+async fn create_branch(
+    forge: &dyn Forge,
+    repo: &Repository,
+    branch: &str,
+    from: Option<&String>,
+) -> Outcome {
+    let from = match from.or(repo.default_branch.as_ref()) {
+        Some(from) => from,
+        None => return Outcome::new(Status::Skipped, "no --from given and no default branch"),
+    };
+    let branches = match forge.list_branches(repo).await {
+        Ok(branches) => branches,
+        Err(e) => {
+            return Outcome::failed(format!("unable to list branches: {}", e.error_message()))
+        }
+    };
+    if branches.iter().any(|existing| existing.name == branch) {
+        info!("Branch `{}` already exists for {}", branch, repo.name);
+        return Outcome::new(Status::NothingToDo, format!("`{}` already exists", branch));
+    }
+    if !branches.iter().any(|existing| &existing.name == from) {
+        error!("Branch `{}` doesn't exist for {}", from, repo.name);
+        return Outcome::failed(format!("`{}` doesn't exist", from));
+    }
+
+    match forge.create_branch(repo, branch, from).await {
+        Ok(_) => {
+            info!("Branch `{}` created successfully on {}", branch, repo.name);
+            Outcome::done(format!("created `{}` from `{}`", branch, from))
+        }
+        Err(e) => {
+            error!(
+                "Error on creating branch `{}` for `{}`: {}",
+                branch,
+                repo.name,
+                e.error_message()
+            );
+            Outcome::failed(format!(
+                "unable to create `{}`: {}",
+                branch,
+                e.error_message()
+            ))
+        }
+    }
+}
+
+async fn delete_branch(
+    forge: &dyn Forge,
+    repo: &Repository,
+    branch: &str,
+    summary: &mut RepoSummary,
+) -> Outcome {
+    if repo.default_branch.as_deref() == Some(branch) {
+        warn!(
+            "Not deleting `{}`, the default branch of {}",
+            branch, repo.name
+        );
+        return Outcome::new(
+            Status::Skipped,
+            format!("`{}` is the default branch", branch),
+        );
+    }
+    let branches = match forge.list_branches(repo).await {
+        Ok(branches) => branches,
+        Err(e) => {
+            return Outcome::failed(format!("unable to list branches: {}", e.error_message()))
+        }
+    };
+    if !branches.iter().any(|existing| existing.name == branch) {
+        info!("No branch `{}` for {}", branch, repo.name);
+        return Outcome::new(Status::NothingToDo, format!("no `{}`", branch));
+    }
+
+    match forge.delete_branch(repo, branch).await {
+        Ok(_) => {
+            summary.branch_deleted = true;
+            Outcome::done(format!("deleted `{}`", branch))
+        }
+        Err(e) => {
+            error!(
+                "Failed to delete branch {}. reason: {}",
+                branch,
+                e.error_message()
+            );
+            Outcome::failed(format!(
+                "unable to delete `{}`: {}",
+                branch,
+                e.error_message()
+            ))
+        }
+    }
+}
+
+async fn find_pull(
+    forge: &dyn Forge,
+    repo: &Repository,
+    from: &str,
+    to: &str,
+) -> Result<Option<PullRequest>, Outcome> {
+    forge.find_pull(repo, from, to).await.map_err(|e| {
+        error!(
+            "Unable to get pull requests for repo {:?}, err: {}",
+            &repo.name,
+            e.error_message()
+        );
+        Outcome::failed(format!(
+            "unable to get pull requests: {}",
+            e.error_message()
+        ))
+    })
+}
+
+type PullOutcome = Result<(Outcome, Option<PullRequest>), Outcome>;
+
+async fn open_pull(
+    forge: &dyn Forge,
+    repo: &Repository,
+    from: &str,
+    to: &str,
+    args: &PrOpenArgs,
+    reviewers: Vec<String>,
+    summary: &mut RepoSummary,
+) -> PullOutcome {
+    let comp = forge.compare_branches(repo, to, from).await.map_err(|e| {
+        error!(
+            "Unable to get comparison between {} and {} : {}",
+            to,
+            from,
+            e.error_message()
+        );
+        Outcome::failed(format!("comparison failed: {}", e.error_message()))
+    })?;
+    summary.compare = Some(comp.status.clone());
+    match comp.status {
+        CompareStatus::Behind | CompareStatus::Diverged => {}
+        _ => {
+            info!("Nothing to merge !");
+            return Ok((Outcome::new(Status::NothingToDo, "nothing to merge"), None));
+        }
+    }
+
+    if let Some(pr) = find_pull(forge, repo, from, to).await? {
+        info!("A matching Pull request already exists");
+        let outcome = Outcome::new(
+            Status::NothingToDo,
+            format!("#{} already open: {}", pr.number, pr.url),
+        );
+        return Ok((outcome, Some(pr)));
+    }
+
+    let title = args.title.clone().unwrap_or_else(|| {
+        format!(
+            "PR for: {}. {} into {}",
+            args.reference.as_deref().unwrap_or_default(),
+            from,
+            to
+        )
+    });
+    let actions = [Action::OpenPull {
+        from: from.to_string(),
+        to: to.to_string(),
+        title,
+        body: None,
+        reviewers,
+    }];
+    Ok(apply_actions(forge, repo, &actions, Duration::ZERO, None, summary).await)
+}
+
+async fn list_pull(forge: &dyn Forge, repo: &Repository, from: &str, to: &str) -> PullOutcome {
+    match find_pull(forge, repo, from, to).await? {
+        Some(pr) => {
+            let state = pr.mergeable_state.as_deref().unwrap_or("open");
+            let outcome = Outcome::done(format!("#{} {}: {}", pr.number, state, pr.url));
+            Ok((outcome, Some(pr)))
+        }
+        None => Ok((Outcome::new(Status::NothingToDo, "no pull request"), None)),
+    }
+}
+
+async fn close_pull(forge: &dyn Forge, repo: &Repository, from: &str, to: &str) -> PullOutcome {
+    let pr = match find_pull(forge, repo, from, to).await? {
+        Some(pr) => pr,
+        None => return Ok((Outcome::new(Status::NothingToDo, "no pull request"), None)),
+    };
+    info!("Closing #{} for {}", pr.number, repo.name);
+    match forge.close_pull(repo, &pr).await {
+        Ok(_) => Ok((Outcome::done(format!("closed #{}", pr.number)), Some(pr))),
+        Err(e) => {
+            error!("Failed to close #{}, {}", pr.number, e.error_message());
+            let failure = Outcome::failed(format!(
+                "unable to close #{}: {}",
+                pr.number,
+                e.error_message()
+            ));
+            Ok((failure, Some(pr)))
+        }
+    }
+}
+
+async fn merge_pull(
+    forge: &dyn Forge,
+    repo: &Repository,
+    from: &str,
+    to: &str,
+    args: &PrMergeArgs,
+    method: Option<MergeMethod>,
+    summary: &mut RepoSummary,
+) -> PullOutcome {
+    let pr = match find_pull(forge, repo, from, to).await? {
+        Some(pr) => pr,
+        None => return Ok((Outcome::new(Status::NothingToDo, "no pull request"), None)),
+    };
+    let decision = plan_merge(repo, pr, method, args.update_branches, args.delete_branches);
+    if decision.actions.is_empty() {
+        return Ok((decision.outcome, decision.pull));
+    }
+    Ok(apply_actions(
+        forge,
+        repo,
+        &decision.actions,
+        Duration::from_secs(args.checks_timeout),
+        decision.pull,
+        summary,
+    )
+    .await)
+}
+
+async fn release(
+    forge: &dyn Forge,
+    repo: &Repository,
+    branch: Option<&String>,
+    args: &ReleaseArgs,
+) -> Outcome {
+    let branch = match branch.or(repo.default_branch.as_ref()) {
+        Some(branch) => branch,
+        None => return Outcome::new(Status::Skipped, "no --branch given and no default branch"),
+    };
+    match forge.find_release(repo, &args.tag).await {
+        Ok(Some(release)) => {
+            info!("`{}` is already released for {}", args.tag, repo.name);
+            return Outcome::new(
+                Status::NothingToDo,
+                format!("`{}` already released: {}", args.tag, release.url),
+            );
+        }
+        Ok(None) => {}
+        Err(e) => {
+            error!(
+                "Unable to get the releases of {}: {}",
+                repo.name,
+                e.error_message()
+            );
+            return Outcome::failed(format!("unable to get releases: {}", e.error_message()));
+        }
+    }
+
+    let name = args.name.as_deref().unwrap_or(&args.tag);
+    info!("Releasing `{}` as `{}` for {}", branch, args.tag, repo.name);
+    match forge
+        .create_release(repo, &args.tag, branch, name, args.draft)
+        .await
+    {
+        Ok(release) if release.draft => {
+            Outcome::done(format!("drafted `{}`: {}", release.tag, release.url))
+        }
+        Ok(release) => Outcome::done(format!("released `{}`: {}", release.tag, release.url)),
+        Err(e) => {
+            error!(
+                "Unable to release `{}` for {}: {}",
+                args.tag,
+                repo.name,
+                e.error_message()
+            );
+            Outcome::failed(format!("unable to release: {}", e.error_message()))
+        }
+    }
+}
+
+fn redact(token: &mut String) {
+    if !token.is_empty() {
+        *token = String::from("***");
+    }
+}
+
+/// The config as JSON, without its tokens.
+pub fn show_config(config: &Config) -> Result<String, String> {
+    let mut config = config.clone();
+    redact(&mut config.token);
+    if let Some(gitlab) = config.gitlab.as_mut() {
+        redact(&mut gitlab.token);
+    }
+    for owner in config.owners.values_mut() {
+        if let Some(token) = owner.token.as_mut() {
+            redact(token);
+        }
+    }
+    serde_json::to_string_pretty(&config).map_err(|e| e.to_string())
+}
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use clap::ValueEnum;
+use forge::{Forge, ForgeError, PullRequest, Repository};
+use serde::Serialize;
+
+use crate::output::print_table;
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConflictingFile {
+    pub filename: String,
+    /// last author of the file on the pull request's head branch
+    pub head_author: Option<String>,
+    /// last author of the file on the pull request's base branch
+    pub base_author: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConflictReport {
+    pub repo: String,
+    /// none for conflicts predicted between branches
+    pub pull_request: Option<u64>,
+    pub url: Option<String>,
+    pub head: String,
+    pub base: String,
+    pub files: Vec<ConflictingFile>,
+}
+
+/// Lists the files touched on both sides of `head` and `base` since the merge
+/// base, with the last author of each file on either side. `pr` is the pull
+/// request of `head` into `base`, if there is one.
+pub async fn build_conflict_report(
+    forge: &dyn Forge,
+    repo: &Repository,
+    head: &str,
+    base: &str,
+    pr: Option<&PullRequest>,
+) -> Result<ConflictReport, ForgeError> {
+    // `compare_branches(repo, a, b)` lists the files changed on `a` since its
+    // merge base with `b`
+    let head_changes = forge.compare_branches(repo, head, base).await?;
+    let base_changes = forge.compare_branches(repo, base, head).await?;
+
+    let base_files: HashSet<&String> = base_changes.files.iter().collect();
+    let mut both_sides: Vec<&String> = head_changes
+        .files
+        .iter()
+        .filter(|filename| base_files.contains(filename))
+        .collect();
+    both_sides.sort();
+    both_sides.dedup();
+
+    let mut files = Vec::with_capacity(both_sides.len());
+    for filename in both_sides {
+        files.push(ConflictingFile {
+            head_author: forge.last_author(repo, head, filename).await?,
+            base_author: forge.last_author(repo, base, filename).await?,
+            filename: filename.clone(),
+        });
+    }
+
+    Ok(ConflictReport {
+        repo: repo.name.clone(),
+        pull_request: pr.map(|pr| pr.number),
+        url: pr.map(|pr| pr.url.clone()),
+        head: head.to_string(),
+        base: base.to_string(),
+        files,
+    })
+}
+
+pub fn print_conflict_reports(
+    reports: &[ConflictReport],
+    format: &ReportFormat,
+    out: &mut dyn Write,
+) {
+    match format {
+        ReportFormat::Json => match serde_json::to_string_pretty(reports) {
+            Ok(json) => {
+                let _ = writeln!(out, "{}", json);
+            }
+            Err(e) => log::error!("Unable to serialize conflict report: {}", e),
+        },
+        ReportFormat::Table => {
+            let mut rows: Vec<[String; 5]> = vec![[
+                String::from("REPO"),
+                String::from("PULL REQUEST"),
+                String::from("FILE"),
+                String::from("HEAD AUTHOR"),
+                String::from("BASE AUTHOR"),
+            ]];
+            for report in reports {
+                let pull = report
+                    .url
+                    .clone()
+                    .unwrap_or_else(|| format!("{} into {}", report.head, report.base));
+                if report.files.is_empty() {
+                    rows.push([
+                        report.repo.clone(),
+                        pull.clone(),
+                        String::from("-"),
+                        String::from("-"),
+                        String::from("-"),
+                    ]);
+                }
+                for file in report.files.iter() {
+                    rows.push([
+                        report.repo.clone(),
+                        pull.clone(),
+                        file.filename.clone(),
+                        file.head_author
+                            .clone()
+                            .unwrap_or_else(|| String::from("-")),
+                        file.base_author
+                            .clone()
+                            .unwrap_or_else(|| String::from("-")),
+                    ]);
+                }
+            }
+
+            print_table(&rows, out);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use config::{Format, TokenSource};
+use github::repos::response::Repo;
+use github::{Github, GithubAPIError};
+use log::info;
+use regex::Regex;
+use serde_json::json;
+
+use crate::select::glob_regex;
+
+#[derive(Args, Debug, Clone)]
+pub struct InitArgs {
+    #[clap(long, value_parser)]
+    /// owner of the repos, a user or an organization, asked for when missing
+    pub owner: Option<String>,
+    #[clap(long, value_parser)]
+    /// glob the names of the repos to manage match, may be repeated; asked for
+    /// when missing
+    pub repos: Vec<String>,
+    #[clap(long, value_parser)]
+    /// GitHub API root, e.g. for GitHub Enterprise
+    pub github_api_url: Option<String>,
+    #[clap(long, value_parser)]
+    /// replace the config file if there is one
+    pub force: bool,
+}
+
+/// Why no config was written, with the exit code it maps to.
+pub struct InitError {
+    pub code: exitcode::ExitCode,
+    pub message: String,
+}
+
+impl InitError {
+    fn new(code: exitcode::ExitCode, message: impl Into<String>) -> InitError {
+        InitError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn api(what: &str, e: Box<dyn GithubAPIError>) -> InitError {
+        InitError::new(
+            exitcode::UNAVAILABLE,
+            format!("unable to get {}: {}", what, e.error_message()),
+        )
+    }
+}
+
+/// The token to call github with: `$MULTIGIT_TOKEN`, else the one of the `gh`
+/// cli, which the config then reads too.
+fn token() -> Result<(String, TokenSource), InitError> {
+    let env: HashMap<String, String> = std::env::vars().collect();
+    let source = match env.get("MULTIGIT_TOKEN").filter(|token| !token.is_empty()) {
+        Some(_) => TokenSource::Env {
+            var: String::from("MULTIGIT_TOKEN"),
+        },
+        None => TokenSource::Gh { host: None },
+    };
+    match source.read(&env) {
+        Ok(token) => Ok((token, source)),
+        Err(message) => Err(InitError::new(
+            exitcode::NOPERM,
+            format!(
+                "no token: set MULTIGIT_TOKEN or log in with `gh auth login` ({})",
+                message
+            ),
+        )),
+    }
+}
+
+/// Asks `question` on stderr and reads the answer from stdin.
+fn ask(question: &str) -> Result<String, InitError> {
+    eprint!("{} ", question);
+    std::io::stderr().flush().ok();
+    let mut answer = String::new();
+    match std::io::stdin().lock().read_line(&mut answer) {
+        Ok(0) | Err(_) => Err(InitError::new(
+            exitcode::USAGE,
+            "no answer, pass --owner and --repos to run without prompts",
+        )),
+        Ok(_) => Ok(answer.trim().to_string()),
+    }
+}
+
+/// Lets the user pick among `me` and the organizations they belong to, by
+/// number or login.
+fn choose_owner(me: &str, orgs: &[String]) -> Result<String, InitError> {
+    let owners: Vec<&str> = std::iter::once(me)
+        .chain(orgs.iter().map(String::as_str))
+        .collect();
+    eprintln!("Owners:");
+    for (index, owner) in owners.iter().enumerate() {
+        let you = if index == 0 { " (you)" } else { "" };
+        eprintln!("  {}) {}{}", index + 1, owner, you);
+    }
+    let answer = ask("Owner of the repos, number or login:")?;
+    match answer.parse::<usize>() {
+        Ok(number) if (1..=owners.len()).contains(&number) => Ok(owners[number - 1].to_string()),
+        _ if !answer.is_empty() => Ok(answer),
+        _ => Err(InitError::new(exitcode::USAGE, "no owner chosen")),
+    }
+}
+
+/// Names matching `globs`, in the order of `names`.
+fn matching(names: &[String], globs: &[String]) -> Result<Vec<String>, InitError> {
+    let patterns = globs
+        .iter()
+        .map(|glob| {
+            Regex::new(&glob_regex(glob)).map_err(|e| {
+                InitError::new(
+                    exitcode::USAGE,
+                    format!("invalid pattern `{}`: {}", glob, e),
+                )
+            })
+        })
+        .collect::<Result<Vec<Regex>, InitError>>()?;
+    Ok(names
+        .iter()
+        .filter(|name| patterns.iter().any(|pattern| pattern.is_match(name)))
+        .cloned()
+        .collect())
+}
+
+/// Lets the user pick repos by number, range (`2-4`) or glob, comma separated.
+fn choose_repos(names: &[String]) -> Result<Vec<String>, InitError> {
+    eprintln!("Repos:");
+    for (index, name) in names.iter().enumerate() {
+        eprintln!("  {}) {}", index + 1, name);
+    }
+    let answer = ask("Repos to manage, e.g. `1,3-5,api-*` or `*`:")?;
+    let mut numbers = Vec::new();
+    let mut globs = Vec::new();
+    for term in answer
+        .split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+    {
+        let (first, last) = term.split_once('-').unwrap_or((term, term));
+        match (first.trim().parse::<usize>(), last.trim().parse::<usize>()) {
+            (Ok(first), Ok(last)) if first >= 1 && first <= last && last <= names.len() => {
+                numbers.extend(first - 1..last)
+            }
+            _ => globs.push(term.to_string()),
+        }
+    }
+    let globbed = matching(names, &globs)?;
+    Ok(names
+        .iter()
+        .enumerate()
+        .filter(|(index, name)| numbers.contains(index) || globbed.contains(name))
+        .map(|(_, name)| name.clone())
+        .collect())
+}
+
+/// Writes a config managing the repos of an owner, asking for what the
+/// arguments leave out; archived repos and forks are never offered.
+pub async fn run(args: &InitArgs, path: Option<&Path>) -> Result<PathBuf, InitError> {
+    let path = path.map_or_else(|| PathBuf::from("config.json"), Path::to_path_buf);
+    // the formats the config is loaded from
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json" | "toml" | "yaml" | "yml") => Format::of(&path),
+        _ => {
+            return Err(InitError::new(
+                exitcode::USAGE,
+                format!(
+                    "{} is not a .json, .toml, .yaml or .yml file",
+                    path.display()
+                ),
+            ))
+        }
+    };
+    if path.exists() && !args.force {
+        return Err(InitError::new(
+            exitcode::CANTCREAT,
+            format!("{} exists, pass --force to replace it", path.display()),
+        ));
+    }
+
+    let (token, token_source) = token()?;
+    let mut gh = Github::new(token, String::new());
+    if let Some(api_url) = &args.github_api_url {
+        gh = gh.with_api_url(api_url.clone());
+    }
+    let me = gh
+        .get_me()
+        .await
+        .map_err(|e| InitError::api("the user", e))?
+        .login;
+    let orgs: Vec<String> = gh
+        .get_my_orgs()
+        .await
+        .map_err(|e| InitError::api("the organizations", e))?
+        .into_iter()
+        .map(|org| org.login)
+        .collect();
+
+    let owner = match &args.owner {
+        Some(owner) => owner.clone(),
+        None => choose_owner(&me, &orgs)?,
+    };
+    let is_user = owner == me || (!orgs.contains(&owner) && gh.get_org(&owner).await.is_err());
+    info!(
+        "{} is {}",
+        owner,
+        if is_user { "a user" } else { "an organization" }
+    );
+
+    // only the user's own listing holds their private repos
+    let repos: Vec<Repo> = match owner == me {
+        true => gh.list_my_repos().await,
+        false => gh.list_repos(&owner, &Some(is_user)).await,
+    }
+    .map_err(|e| InitError::api("the repos", e))?;
+    let names: Vec<String> = repos
+        .into_iter()
+        .filter(|repo| !repo.archived && !repo.disabled && !repo.fork)
+        .map(|repo| repo.name)
+        .collect();
+    let chosen = match args.repos.is_empty() {
+        true => choose_repos(&names)?,
+        false => matching(&names, &args.repos)?,
+    };
+    if chosen.is_empty() {
+        return Err(InitError::new(exitcode::USAGE, "no repo chosen"));
+    }
+
+    let mut config = json!({
+        "org_name": owner,
+        "is_user": is_user,
+        "token_source": token_source,
+        "repos": chosen,
+    });
+    if let Some(api_url) = &args.github_api_url {
+        config["github_api_url"] = json!(api_url);
+    }
+    // what is written has to load
+    let text = format
+        .serialize(&config)
+        .and_then(|text| format.parse(&text).map(|_| text))
+        .map_err(|message| InitError::new(exitcode::SOFTWARE, message))?;
+    std::fs::write(&path, text).map_err(|e| {
+        InitError::new(
+            exitcode::CANTCREAT,
+            format!("unable to write {}: {}", path.display(), e),
+        )
+    })?;
+    info!(
+        "{} repos of {} written to {}",
+        chosen.len(),
+        owner,
+        path.display()
+    );
+    Ok(path)
+}
@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::io::Write;
+
+use env_logger::Env;
+use log::{Log, Metadata, Record};
+
+tokio::task_local! {
+    /// lines logged by the repo being processed
+    static GROUP: RefCell<Vec<String>>;
+}
+
+/// Logs like `env_logger`, except within [`grouped`] where lines are held
+/// back and printed together once the group is done, so repos processed
+/// concurrently don't interleave their output.
+struct GroupedLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for GroupedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        let line = format!(
+            "[{:<5} {}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if GROUP
+            .try_with(|lines| lines.borrow_mut().push(line))
+            .is_err()
+        {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+pub fn init() {
+    let inner = env_logger::Builder::from_env(Env::default().default_filter_or("info")).build();
+    log::set_max_level(inner.filter());
+    log::set_boxed_logger(Box::new(GroupedLogger { inner })).expect("Logger already set");
+}
+
+/// Runs `future`, then prints what it logged in one block under `title`.
+pub async fn grouped<F: Future>(title: &str, future: F) -> F::Output {
+    let (output, lines) = GROUP
+        .scope(RefCell::new(vec![]), async {
+            let output = future.await;
+            (output, GROUP.with(|lines| lines.take()))
+        })
+        .await;
+
+    let mut block = format!("== {} ==\n", title);
+    for line in lines {
+        block.push_str(&line);
+        block.push('\n');
+    }
+    let _ = std::io::stderr().lock().write_all(block.as_bytes());
+    output
+}
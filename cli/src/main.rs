@@ -1,29 +1,109 @@
-use clap::Parser;
-use config::{load_config, Config};
-use env_logger::Env;
-use github::branches::response::Branch;
-use github::commits::response::CompareStatus;
-use github::pulls::response::PullRequest;
-use github::repos::response::Repo;
-use github::Github;
+mod apply;
+mod commands;
+mod conflicts;
+mod init;
+mod logging;
+mod output;
+mod plan;
+mod select;
+mod status;
+mod summary;
+
+use apply::apply_actions;
+use clap::{Args, Parser, Subcommand};
+use commands::{BranchPair, ConfigCommand, RepoCommand};
+use config::{Config, ConfigLoader, MergeMethod, Provider, RepoConfig, Selector, Stage};
+use conflicts::{build_conflict_report, print_conflict_reports, ConflictReport, ReportFormat};
+use forge::{Branch, CompareStatus, Comparison, Forge, PullRequest, Repository};
+use futures::stream::{self, StreamExt};
+use gitea::Gitea;
+use github::{Cassette, Github, GithubForge};
+use gitlab::Gitlab;
+use local_git::{LocalGit, MergePrediction};
 use log::debug;
 use log::error;
 use log::info;
 use log::warn;
+use output::OutputFormat;
+use plan::{print_plan, Action, Plan, RepoPlan};
+use status::{fill_status, print_statuses, pull_checks, pull_review, RepoStatus};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use summary::{exit_code, print_results, Outcome, RepoSummary, Status};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, long_about=None)]
-struct Aargs {
-    #[clap(long, value_parser)]
-    /// source branch to create pull request
-    from: String,
+struct Cli {
+    #[clap(flatten)]
+    global: GlobalArgs,
+    #[clap(subcommand)]
+    command: Command,
+}
 
-    #[clap(long, value_parser)]
-    /// destination branch to create pull request
-    to: String,
-    #[clap(long, value_parser)]
+/// Arguments of every command.
+#[derive(Args, Debug, Clone)]
+struct GlobalArgs {
+    #[clap(long, value_parser, global = true)]
+    /// config file, else `$MULTIGIT_CONFIG`, `./config.json` then `$XDG_CONFIG_HOME/multigit/config.json`
+    config: Option<PathBuf>,
+    #[clap(long, value_parser, global = true)]
+    /// config profile to apply, else `$MULTIGIT_PROFILE`
+    profile: Option<String>,
+    #[clap(long, value_parser, global = true)]
+    /// only manage the repos of this group, may be repeated
+    group: Vec<String>,
+    #[clap(long, value_parser, global = true)]
+    /// manage the listed repos matching `topic=backend,language=rust,exclude=sandbox`
+    /// instead of the config's, may be repeated
+    select: Vec<Selector>,
+    #[clap(long, value_parser, global = true)]
+    /// run against a directory of bare repositories (`<repo>.git`) instead of github
+    local: Option<PathBuf>,
+    #[clap(long, value_parser, default_value_t = 4, global = true)]
+    /// number of repos processed concurrently
+    jobs: usize,
+    #[clap(long, value_enum, default_value = "table", global = true)]
+    /// how the per-repo results are printed, `json` and `csv` leave stdout to them
+    output: OutputFormat,
+    #[clap(long, value_parser, global = true)]
+    /// record the github requests and responses to `<dir>/<owner>.json`, tokens redacted
+    record: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// promote `from` into `to` on every repo: branches, pull requests and merges
+    Promote(Box<PromoteArgs>),
+    /// print where each repo is: branches, ahead/behind counts, open pull request, reviews and checks
+    Status(BranchPair),
+    #[clap(flatten)]
+    Repo(RepoCommand),
+    /// inspect the config
+    #[clap(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Args, Debug, Clone)]
+struct PromoteArgs {
+    #[clap(
+        value_parser,
+        requires = "stage",
+        conflicts_with_all = &["source", "destination", "apply"]
+    )]
+    /// pipeline of the config to run, promoting into `--stage` with its rules
+    pipeline: Option<String>,
+    #[clap(long, value_parser, requires = "pipeline")]
+    /// stage of the pipeline promoted into, from the stage before it
+    stage: Option<String>,
+    #[clap(flatten)]
+    branches: BranchPair,
+    #[clap(long, value_parser, required_unless_present_any = &["apply", "pipeline"])]
     /// reference branch: org/project#issue_number
-    reference: String,
+    reference: Option<String>,
 
     #[clap(long, value_parser)]
     /// create pull requests if not existing
@@ -33,281 +113,1216 @@ struct Aargs {
     /// merge pull requests
     merge: bool,
     #[clap(long, value_parser)]
-    /// merge pull requests
-    list: bool,
-    #[clap(long, value_parser)]
     /// create branches if they don't exist
     create_branches: bool,
     #[clap(long, value_parser)]
     /// delete branches after merge
     delete_branches: bool,
+    #[clap(long, value_parser)]
+    /// open back-merge pull requests (to into from) where `to` has commits missing from `from`
+    back_merge: bool,
+    #[clap(long, value_parser)]
+    /// merge `from` into `to` directly, without a pull request
+    direct: bool,
+    #[clap(long, value_parser)]
+    /// update pull requests that are behind their base branch, wait for checks, then merge
+    update_branches: bool,
+    #[clap(long, value_parser, default_value_t = 600)]
+    /// seconds to wait for an updated pull request to be ready to merge
+    checks_timeout: u64,
+    #[clap(long, value_enum)]
+    /// report the files touched on both sides of conflicting pull requests
+    conflict_report: Option<ReportFormat>,
+    #[clap(long, value_parser)]
+    /// predict merge conflicts with a local three-way merge first, nothing is
+    /// written when one is predicted
+    predict_conflicts: bool,
+    #[clap(long, value_parser, default_value = ".multigit-cache")]
+    /// directory where repositories are fetched to predict conflicts
+    cache_dir: PathBuf,
+    #[clap(long, value_parser)]
+    /// make every read, then print the writes a run would make instead of making them
+    dry_run: bool,
+    #[clap(long, value_parser, requires = "dry-run")]
+    /// save the plan of a dry run to a file, for `--apply`
+    save_plan: Option<PathBuf>,
+    #[clap(long, value_parser, conflicts_with = "dry-run")]
+    /// make the writes of a plan saved with `--save-plan`, nothing else
+    apply: Option<PathBuf>,
+
+    /// source branch of the repo being processed
+    #[clap(skip)]
+    from: String,
+    /// destination branch of the repo being processed
+    #[clap(skip)]
+    to: String,
+    /// the repo's `merge_method`
+    #[clap(skip)]
+    merge_method: Option<MergeMethod>,
+    /// the repo's `reviewers`
+    #[clap(skip)]
+    reviewers: Vec<String>,
+    /// the stage's `require_approval`
+    #[clap(skip)]
+    require_approval: bool,
+    /// the stage's `require_checks`
+    #[clap(skip)]
+    require_checks: bool,
+    /// the stage's `tag`, for the day
+    #[clap(skip)]
+    tag: Option<String>,
 }
 
-fn check_branch_in(branch_name: &String, branches: &Vec<Branch>) -> bool {
+impl PromoteArgs {
+    fn reference(&self) -> &str {
+        self.reference.as_deref().unwrap_or_default()
+    }
+
+    /// The stage promoted from and the one promoted into, when running a
+    /// pipeline.
+    fn hop<'a>(&self, config: &'a Config) -> Result<Option<(&'a Stage, &'a Stage)>, String> {
+        let name = match &self.pipeline {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+        let pipeline = config
+            .pipelines
+            .get(name)
+            .ok_or_else(|| format!("no pipeline `{}`", name))?;
+        pipeline
+            .hop(self.stage.as_deref().unwrap_or_default())
+            .map(Some)
+    }
+
+    /// Arguments for `repo`, with `--from` and `--to` resolved, or the
+    /// branches and rules of the pipeline's stage, and the repo's overrides
+    /// applied.
+    fn for_repo(
+        &self,
+        repo: &Repository,
+        config: &Config,
+        repo_config: &RepoConfig,
+    ) -> Result<PromoteArgs, String> {
+        let overrides = repo_config.overrides().cloned().unwrap_or_default();
+        let mut args = PromoteArgs {
+            create_branches: overrides.create_branches.unwrap_or(self.create_branches),
+            merge_method: overrides.merge_method,
+            reviewers: overrides.reviewers,
+            ..self.clone()
+        };
+        match self.hop(config)? {
+            Some((previous, stage)) => {
+                args.from = config.branch_of(repo_config, &previous.branch)?;
+                args.to = config.branch_of(repo_config, &stage.branch)?;
+                args.create_pulls = true;
+                args.merge = true;
+                args.merge_method = args.merge_method.or(stage.merge_method);
+                args.delete_branches = stage.delete_branch;
+                args.require_approval = stage.require_approval;
+                args.require_checks = stage.require_checks;
+                let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+                args.tag = stage.tag.as_ref().map(|tag| tag.replace("{date}", &today));
+                if args.reference.is_none() {
+                    args.reference = Some(format!("{} {}", previous.name, stage.name));
+                }
+            }
+            None => (args.from, args.to) = self.branches.resolve(repo, config, repo_config)?,
+        }
+        Ok(args)
+    }
+}
+
+fn check_branch_in(branch_name: &str, branches: &[Branch]) -> bool {
     branches
         .iter()
         .map(|b| b.name.clone())
-        .any(|b_name| b_name == branch_name.as_str())
+        .any(|b_name| b_name == branch_name)
 }
 
-async fn get_or_create_pull_request(
-    gh: &Github,
-    repo: &Repo,
-    owner: String,
-    args: &Aargs,
-) -> Option<PullRequest> {
-    let pulls: Vec<PullRequest> = match gh.list_pulls(&repo, &args.from, &args.to).await {
-        Ok(pulls) => pulls,
+/// What a repo needs, decided from reads only: the writes to make, or the
+/// outcome when there are none.
+struct Decision {
+    actions: Vec<Action>,
+    /// the pull request the actions start from
+    pull: Option<PullRequest>,
+    outcome: Outcome,
+}
+
+impl Decision {
+    fn stop(outcome: Outcome) -> Decision {
+        Decision {
+            actions: vec![],
+            pull: None,
+            outcome,
+        }
+    }
+
+    fn act(actions: Vec<Action>) -> Decision {
+        Decision {
+            actions,
+            pull: None,
+            outcome: Outcome::new(Status::NothingToDo, ""),
+        }
+    }
+}
+
+async fn compare(
+    forge: &dyn Forge,
+    repo: &Repository,
+    args: &PromoteArgs,
+    summary: &mut RepoSummary,
+) -> Result<Comparison, Outcome> {
+    let (base, head) = (&args.to, &args.from);
+    let comp = forge
+        .compare_branches(repo, base, head)
+        .await
+        .map_err(|e| {
+            error!(
+                "Unable to get comparison between {} and {} : {}",
+                base,
+                head,
+                e.error_message()
+            );
+            if let Some(extra_info) = e.extra_info() {
+                debug!("{}", extra_info);
+            }
+            Outcome::failed(format!("comparison failed: {}", e.error_message()))
+        })?;
+    summary.compare = Some(comp.status.clone());
+    Ok(comp)
+}
+
+/// Deletes `branch` once merged, unless it is the default branch of the repo,
+/// which is never deleted.
+fn delete_merged(repo: &Repository, branch: &str) -> Option<Action> {
+    if repo.default_branch.as_deref() == Some(branch) {
+        warn!(
+            "Not deleting `{}`, the default branch of {}",
+            branch, repo.name
+        );
+        return None;
+    }
+    Some(Action::DeleteBranch {
+        branch: branch.to_string(),
+    })
+}
+
+/// Merges `pr` with `method`, once brought up to date with `update_branches`.
+fn plan_merge(
+    repo: &Repository,
+    pr: PullRequest,
+    method: Option<MergeMethod>,
+    update_branches: bool,
+    delete_branches: bool,
+) -> Decision {
+    let mut actions = vec![];
+    if pr.mergeable_state.as_deref() == Some("behind") {
+        if update_branches {
+            // mergeability is only known once the update is done
+            actions.push(Action::UpdatePull { number: pr.number });
+        } else {
+            warn!(
+                "{} is behind {}, use --update-branches to bring it up to date before merging",
+                pr.head, pr.base
+            );
+        }
+    }
+
+    if actions.is_empty() {
+        debug!("is mergeable ? {:?}", pr.mergeable.unwrap_or(false));
+        let outcome = match pr.mergeable {
+            Some(true) => None,
+            Some(false) => {
+                warn!("Pull request is not mergeable, there is a conflict");
+                Some(Outcome::new(
+                    Status::Conflict,
+                    format!("#{} has conflicts", pr.number),
+                ))
+            }
+            None => {
+                info!("Unable to know if it's mergeable, please try later");
+                Some(Outcome::new(
+                    Status::Pending,
+                    format!("#{} mergeability unknown, try later", pr.number),
+                ))
+            }
+        };
+        if let Some(outcome) = outcome {
+            return Decision {
+                actions,
+                pull: Some(pr),
+                outcome,
+            };
+        }
+    }
+
+    actions.push(Action::MergePull {
+        number: Some(pr.number),
+        method,
+    });
+    if delete_branches {
+        actions.extend(delete_merged(repo, &pr.head));
+    }
+    Decision {
+        pull: Some(pr),
+        ..Decision::act(actions)
+    }
+}
+
+/// Why `pr` can't be merged yet under the stage's `require_approval` and
+/// `require_checks`, if it can't.
+async fn unmet_requirements(
+    forge: &dyn Forge,
+    repo: &Repository,
+    pr: &PullRequest,
+    args: &PromoteArgs,
+) -> Option<Outcome> {
+    if args.require_approval {
+        let review = match forge.list_reviews(repo, pr.number).await {
+            Ok(reviews) => pull_review(reviews),
+            Err(e) => {
+                return Some(Outcome::failed(format!(
+                    "unable to get reviews: {}",
+                    e.error_message()
+                )))
+            }
+        };
+        if review != "approved" {
+            info!("#{} is not approved, review: {}", pr.number, review);
+            return Some(Outcome::new(
+                Status::Pending,
+                format!("#{} awaits approval ({})", pr.number, review),
+            ));
+        }
+    }
+    if args.require_checks {
+        let checks = match forge.list_checks(repo, &pr.head_sha).await {
+            Ok(checks) => pull_checks(checks),
+            Err(e) => {
+                return Some(Outcome::failed(format!(
+                    "unable to get checks: {}",
+                    e.error_message()
+                )))
+            }
+        };
+        match checks {
+            "failure" => {
+                warn!("The checks of #{} failed", pr.number);
+                return Some(Outcome::failed(format!("#{} checks failed", pr.number)));
+            }
+            "pending" => {
+                info!("The checks of #{} are still running", pr.number);
+                return Some(Outcome::new(
+                    Status::Pending,
+                    format!("#{} checks pending", pr.number),
+                ));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Opens the pull request of `from` into `to` unless there is one, and merges
+/// it with `--merge`.
+async fn plan_promotion(
+    forge: &dyn Forge,
+    repo: &Repository,
+    args: &PromoteArgs,
+    summary: &mut RepoSummary,
+) -> Decision {
+    if !args.create_pulls {
+        if args.merge {
+            info!("No pull requests to merge for {}", repo.name);
+        }
+        return Decision::stop(Outcome::new(Status::NothingToDo, ""));
+    }
+
+    info!("Comparing {} and {} for PR", args.to, args.from);
+    let comp = match compare(forge, repo, args, summary).await {
+        Ok(comp) => comp,
+        Err(failure) => return Decision::stop(failure),
+    };
+
+    info!("`{}` is {:?} to `{}`", args.to, comp.status, args.from);
+    match comp.status {
+        CompareStatus::Behind | CompareStatus::Diverged => {}
+        _ => {
+            info!("Nothing to merge !");
+            return Decision::stop(Outcome::new(Status::NothingToDo, "nothing to merge"));
+        }
+    };
+
+    let existing_pr = match forge.find_pull(repo, &args.from, &args.to).await {
+        Ok(existing_pr) => existing_pr,
         Err(e) => {
             error!(
                 "Unable to get pull requests for repo {:?}, err: {}",
                 &repo.name,
                 e.error_message()
             );
-            // TODO: how should this be handled ?
-            std::process::exit(-1);
+            return Decision::stop(Outcome::failed(format!(
+                "unable to get pull requests: {}",
+                e.error_message()
+            )));
         }
     };
 
-    let existing_pr = pulls.iter().find(|pr| {
-        pr.head.label == format!("{}:{}", owner, args.from)
-            && pr.base.label == format!("{}:{}", owner, args.to)
-    });
-
     debug!("Matched prs: {:?}", existing_pr);
 
     match existing_pr {
-        Some(pull_request) => {
-            let full_pr: Option<PullRequest> = match gh.get_pull(&repo, pull_request.number).await {
-                Ok(pr) => {
-                    info!("A matching Pull request already exists");
-                    Some(pr)
+        Some(pr) => {
+            info!("A matching Pull request already exists");
+            if args.merge {
+                if let Some(outcome) = unmet_requirements(forge, repo, &pr, args).await {
+                    return Decision {
+                        outcome,
+                        pull: Some(pr),
+                        actions: vec![],
+                    };
                 }
-                Err(e) => {
-                    error!("Unable to get pull {:?}", e.error_message());
-                    None
+                plan_merge(
+                    repo,
+                    pr,
+                    args.merge_method,
+                    args.update_branches,
+                    args.delete_branches,
+                )
+            } else {
+                Decision {
+                    outcome: Outcome::done(format!("#{} open: {}", pr.number, pr.url)),
+                    pull: Some(pr),
+                    actions: vec![],
                 }
-            };
-            full_pr
+            }
         }
         None => {
-            if args.create_pulls {
-                return match gh
-                    .create_pull(&repo, &args.from, &args.to, &args.reference)
-                    .await
-                {
-                    Ok(new_pull_request) => Some(new_pull_request),
-                    Err(e) => {
-                        error!("Unable to create a new PR {}", e.error_message());
-                        None
-                    }
-                };
+            let mut actions = vec![Action::OpenPull {
+                from: args.from.clone(),
+                to: args.to.clone(),
+                title: format!(
+                    "PR for: {}. {} into {}",
+                    args.reference(),
+                    args.from,
+                    args.to
+                ),
+                body: None,
+                reviewers: args.reviewers.clone(),
+            }];
+            if args.merge && (args.require_approval || args.require_checks) {
+                info!("The new pull request is merged once its requirements are met");
+            } else if args.merge {
+                actions.push(Action::MergePull {
+                    number: None,
+                    method: args.merge_method,
+                });
+                if args.delete_branches {
+                    actions.extend(delete_merged(repo, &args.from));
+                }
             }
-            None
+            Decision::act(actions)
         }
     }
 }
 
-async fn merge_and_delete(gh: &Github, pr: &PullRequest, args: &Aargs) {
-    let repo: &Repo = pr.base.repo.as_ref().unwrap();
+/// Merges `from` into `to` without a pull request.
+async fn plan_direct(
+    forge: &dyn Forge,
+    repo: &Repository,
+    args: &PromoteArgs,
+    summary: &mut RepoSummary,
+) -> Decision {
+    let comp = match compare(forge, repo, args, summary).await {
+        Ok(comp) => comp,
+        Err(failure) => return Decision::stop(failure),
+    };
+    match comp.status {
+        CompareStatus::Behind | CompareStatus::Diverged => {}
+        _ => {
+            info!("Nothing to merge !");
+            return Decision::stop(Outcome::new(Status::NothingToDo, "nothing to merge"));
+        }
+    }
+
+    let mut actions = vec![Action::MergeBranches {
+        from: args.from.clone(),
+        to: args.to.clone(),
+        message: format!(
+            "Merge {} into {} for: {}",
+            args.from,
+            args.to,
+            args.reference()
+        ),
+    }];
+    if args.delete_branches {
+        actions.extend(delete_merged(repo, &args.from));
+    }
+    Decision::act(actions)
+}
 
-    info!(
-        "Merging {} into {} for {}",
-        pr.head.label, pr.base.label, repo.name
+async fn predict_conflicts(
+    local_git: &LocalGit,
+    repo: &Repository,
+    token: &str,
+    args: &PromoteArgs,
+) -> Outcome {
+    // git2 blocks, the fetch and merge run off the async workers; cached as
+    // `<owner>/<name>.git`, owners may have repos of the same name
+    let (local_git, name, url) = (
+        local_git.clone(),
+        repo.full_name.clone(),
+        repo.clone_url.clone(),
     );
+    let (from, to, token) = (args.from.clone(), args.to.clone(), token.to_string());
+    let predicted = tokio::task::spawn_blocking(move || {
+        if let Err(e) = local_git.fetch(&name, &url, &[&from, &to], Some(&token)) {
+            return Err(format!("fetch failed: {}", e.error_message()));
+        }
+        local_git
+            .predict_merge(&name, &from, &to)
+            .map_err(|e| format!("prediction failed: {}", e.error_message()))
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("prediction failed: {}", e)));
 
-    debug!("is mergeable ? {:?}", pr.mergeable.unwrap_or(false));
-
-    match pr.mergeable {
-        Some(mergeable) => {
-            if mergeable {
-                let merge_status = gh.merge_pull(repo, pr).await;
-                match merge_status {
-                    Ok(merge_status) => {
-                        if merge_status.merged == true && args.delete_branches {
-                            if let Err(e) = gh.delete_reference(repo, &args.from).await {
-                                error!(
-                                    "Failed to delete branch {}. reason: {}",
-                                    args.from,
-                                    e.error_message()
-                                );
-                                if let Some(extra_info) = e.extra_info() {
-                                    debug!("original response: {:?}", extra_info)
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to merge #{}, {}", pr.number, e.error_message());
-                        if let Some(extra_info) = e.extra_info() {
-                            debug!("original response: {:?}", extra_info);
-                        }
-                    }
-                }
-            } else {
-                warn!("Pull request is not mergeable, there is a conflict");
-            }
+    match predicted {
+        Ok(MergePrediction::UpToDate) => {
+            info!("Nothing to merge !");
+            Outcome::new(Status::NothingToDo, "nothing to merge")
         }
-        None => {
-            info!("Unable to know if it's mergeable, please try later");
+        Ok(MergePrediction::FastForward) | Ok(MergePrediction::Clean) => {
+            info!(
+                "`{}` would merge cleanly into `{}` for {}",
+                args.from, args.to, repo.name
+            );
+            Outcome::done("would merge cleanly")
+        }
+        Ok(MergePrediction::Conflict(files)) => {
+            warn!(
+                "`{}` into `{}` would conflict in repo {} on files: {}",
+                args.from,
+                args.to,
+                repo.name,
+                files.join(", ")
+            );
+            Outcome::new(
+                Status::Conflict,
+                format!("would conflict on {}", files.join(", ")),
+            )
+        }
+        Err(e) => {
+            error!("Unable to predict merge for {}: {}", repo.name, e);
+            Outcome::failed(e)
         }
     }
 }
 
-#[tokio::main]
-async fn main() {
-    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
-    let args = Aargs::parse();
-    let config: Config = match load_config() {
-        Ok(config) => config,
+fn back_merge_body(comp: &Comparison, from: &str, to: &str) -> String {
+    let mut body = format!("Commits on `{}` missing from `{}`:\n\n", to, from);
+    for commit in comp.commits.iter() {
+        let summary = commit.message.lines().next().unwrap_or("");
+        let author = commit.author.clone().unwrap_or_default();
+        let short_sha = &commit.sha[..commit.sha.len().min(7)];
+        body.push_str(&format!("- {} {} ({})\n", short_sha, summary, author));
+    }
+
+    let listed = comp.commits.len() as u64;
+    if comp.ahead_by > listed {
+        body.push_str(&format!("- ... and {} more\n", comp.ahead_by - listed));
+    }
+    body
+}
+
+/// Opens a pull request of `to` into `from` when `to` has commits `from` is missing.
+async fn plan_back_merge(
+    forge: &dyn Forge,
+    repo: &Repository,
+    args: &PromoteArgs,
+    summary: &mut RepoSummary,
+) -> Decision {
+    info!("Comparing {} and {} for back-merge", args.from, args.to);
+    // Same comparison as the promotion, read the other way round: `to` being ahead
+    // of `from` means it carries commits (e.g. hotfixes) that `from` is missing.
+    let comp = match compare(forge, repo, args, summary).await {
+        Ok(comp) => comp,
+        Err(failure) => return Decision::stop(failure),
+    };
+
+    match comp.status {
+        CompareStatus::Ahead | CompareStatus::Diverged => {
+            info!(
+                "`{}` has {} commit(s) missing from `{}` on {}",
+                args.to, comp.ahead_by, args.from, repo.name
+            );
+        }
+        _ => {
+            info!("Nothing to back-merge for {}", repo.name);
+            return Decision::stop(Outcome::new(Status::NothingToDo, "nothing to back-merge"));
+        }
+    }
+
+    match forge.find_pull(repo, &args.to, &args.from).await {
+        Ok(Some(pr)) => {
+            info!(
+                "A back-merge pull request already exists for {}: {}",
+                repo.name, pr.url
+            );
+            return Decision::stop(Outcome::done(format!(
+                "back-merge #{} already open: {}",
+                pr.number, pr.url
+            )));
+        }
+        Ok(None) => {}
         Err(e) => {
-            error!("{}", e);
-            std::process::exit(-1);
+            error!(
+                "Unable to get pull requests for repo {:?}, err: {}",
+                &repo.name,
+                e.error_message()
+            );
+            return Decision::stop(Outcome::failed(format!(
+                "unable to get pull requests: {}",
+                e.error_message()
+            )));
         }
-    };
+    }
 
-    info!("Managing {}", config.org_name);
+    Decision::act(vec![Action::OpenPull {
+        from: args.to.clone(),
+        to: args.from.clone(),
+        title: format!(
+            "Back-merge for: {}. {} into {}",
+            args.reference(),
+            args.to,
+            args.from
+        ),
+        body: Some(back_merge_body(&comp, &args.from, &args.to)),
+        reviewers: args.reviewers.clone(),
+    }])
+}
 
-    let gh = Github::new(config.token, config.org_name.clone());
+/// Forge hosting the repos of `owner`, with the token to clone them.
+fn build_forge(
+    config: &Config,
+    provider: Provider,
+    owner: &String,
+    args: &GlobalArgs,
+) -> Result<(Box<dyn Forge>, String), String> {
+    let owner_config = config.owners.get(owner);
+    let url = owner_config.and_then(|owner_config| owner_config.url.clone());
+    let token = owner_config
+        .and_then(|owner_config| owner_config.token.clone())
+        .unwrap_or_else(|| config.token.clone());
 
-    for repo_name in config.repos {
-        let repo = match gh.get_repo(&repo_name).await {
-            Ok(repo) => repo,
-            Err(e) => {
-                warn!("Unable to get repo {repo_name}: {:?}", e.error_message());
-                if let Some(extra_info) = e.extra_info() {
-                    debug!("{extra_info}");
-                }
-                continue;
-            }
-        };
+    if let Some(root) = &args.local {
+        return Ok((Box::new(LocalGit::new(root.clone())), token));
+    }
 
-        info!("Processing repo: {}", repo.name);
+    match (provider, url) {
+        (Provider::Github, url) => {
+            let mut gh = Github::new(token.clone(), owner.clone());
+            if let Some(api_url) = url.or_else(|| config.github_api_url.clone()) {
+                gh = gh.with_api_url(api_url);
+            }
+            if let Some(dir) = &args.record {
+                gh = gh.with_cassette(Cassette::record(&dir.join(format!("{owner}.json"))));
+            }
+            Ok((Box::new(GithubForge::new(gh)), token))
+        }
+        (Provider::Gitlab, Some(url)) => Ok((
+            Box::new(Gitlab::new(url, token.clone(), owner.clone())),
+            token,
+        )),
+        (Provider::Gitlab, None) => match &config.gitlab {
+            Some(gitlab) => Ok((
+                Box::new(Gitlab::new(
+                    gitlab.url.clone(),
+                    gitlab.token.clone(),
+                    gitlab.group.clone(),
+                )),
+                gitlab.token.clone(),
+            )),
+            None => Err(String::from("no gitlab section in the config")),
+        },
+        (Provider::Gitea, Some(url)) => Ok((
+            Box::new(Gitea::new(url, token.clone(), owner.clone())),
+            token,
+        )),
+        (Provider::Gitea, None) => Err(format!("no url for the gitea owner {owner}")),
+    }
+}
 
-        let branches: Vec<Branch> = match gh.list_branches(&repo).await {
-            Ok(branches) => branches,
-            Err(e) => {
-                error!(
-                    "Couldn't get branches for repo {:?}, error: {}. Skipping ...",
-                    &repo.name,
-                    e.error_message()
-                );
+type Forges = HashMap<(Provider, String), Result<(Box<dyn Forge>, String), String>>;
 
-                if let Some(extra_info) = e.extra_info() {
-                    debug!("{}", extra_info);
-                }
+fn forge_of<'a>(
+    config: &Config,
+    forges: &'a Forges,
+    repo_config: &RepoConfig,
+) -> &'a Result<(Box<dyn Forge>, String), String> {
+    let key = (
+        config.provider_of(repo_config),
+        config.owner_of(repo_config).to_string(),
+    );
+    &forges[&key]
+}
 
-                // TODO: should we skip or abort ?
-                continue;
+/// The forge of a repo, its token and the repo, or why it can't be worked on.
+async fn open_repo<'a>(
+    config: &Config,
+    forges: &'a Forges,
+    repo_config: &RepoConfig,
+) -> Result<(&'a dyn Forge, &'a String, Repository), Outcome> {
+    let repo_name = repo_config.name();
+    let (forge, token) = match forge_of(config, forges, repo_config) {
+        Ok(forge) => forge,
+        Err(e) => {
+            warn!("Skipping {repo_name}: {e}");
+            return Err(Outcome::new(Status::Skipped, e.clone()));
+        }
+    };
+    let repo = match forge.get_repo(repo_name).await {
+        Ok(repo) => repo,
+        Err(e) => {
+            warn!("Unable to get repo {repo_name}: {:?}", e.error_message());
+            if let Some(extra_info) = e.extra_info() {
+                debug!("{extra_info}");
             }
-        };
+            return Err(Outcome::failed(format!(
+                "unable to get repo: {}",
+                e.error_message()
+            )));
+        }
+    };
+
+    if repo.archived {
+        info!("Skipping archived repo {}", repo.name);
+        return Err(Outcome::new(Status::Skipped, "archived"));
+    }
+    Ok((forge.as_ref(), token, repo))
+}
+
+/// Runs `f` on each repo, `jobs` at a time, with the logs of each repo
+/// grouped; the results come back in the order of the repos.
+async fn for_each_repo<'a, T, F, Fut>(repos: &[&'a RepoConfig], jobs: usize, f: F) -> Vec<T>
+where
+    F: Fn(&'a RepoConfig) -> Fut,
+    Fut: Future<Output = T>,
+{
+    stream::iter(repos.iter())
+        .map(|repo_config| logging::grouped(repo_config.name(), f(repo_config)))
+        .buffered(jobs.max(1))
+        .collect()
+        .await
+}
+
+async fn list_repo(
+    config: &Config,
+    forges: &Forges,
+    repo_config: &RepoConfig,
+    branches: &BranchPair,
+) -> RepoStatus {
+    let mut status = RepoStatus::new(repo_config.name().to_string());
+    let (forge, _, repo) = match open_repo(config, forges, repo_config).await {
+        Ok(opened) => opened,
+        Err(outcome) => {
+            status.error = Some(outcome.detail);
+            return status;
+        }
+    };
+    let (from, to) = match branches.resolve(&repo, config, repo_config) {
+        Ok(branches) => branches,
+        Err(e) => {
+            status.error = Some(e);
+            return status;
+        }
+    };
 
-        for branch in branches.iter() {
-            debug!("{}, branch: {}", repo.name, branch.name);
+    if let Err(e) = fill_status(forge, &repo, &from, &to, &mut status).await {
+        error!("Unable to get the status of {}: {}", repo.name, e);
+        status.error = Some(e);
+    }
+    status
+}
+
+/// Runs one of the [`RepoCommand`]s on a repo.
+async fn command_repo(
+    config: &Config,
+    forges: &Forges,
+    repo_config: &RepoConfig,
+    command: &RepoCommand,
+) -> RepoSummary {
+    let mut summary = RepoSummary::new(repo_config.name().to_string());
+    let outcome = match open_repo(config, forges, repo_config).await {
+        Ok((forge, _, repo)) => {
+            commands::run(forge, &repo, config, repo_config, command, &mut summary).await
         }
+        Err(outcome) => outcome,
+    };
+    if outcome.status == Status::Failed {
+        summary.errors.push(outcome.detail.clone());
+    }
+    summary.outcome = outcome;
+    summary
+}
+
+/// What processing a repo produced.
+struct RepoRun {
+    summary: RepoSummary,
+    conflict_report: Option<ConflictReport>,
+    plan: Option<RepoPlan>,
+}
+
+async fn process_repo(
+    config: &Config,
+    forges: &Forges,
+    local_git: &LocalGit,
+    repo_config: &RepoConfig,
+    args: &PromoteArgs,
+    saved_plan: Option<&RepoPlan>,
+) -> RepoRun {
+    let mut run = RepoRun {
+        summary: RepoSummary::new(repo_config.name().to_string()),
+        conflict_report: None,
+        plan: None,
+    };
+    let outcome = promote_repo(
+        config,
+        forges,
+        local_git,
+        repo_config,
+        args,
+        saved_plan,
+        &mut run,
+    )
+    .await;
+    if outcome.status == Status::Failed {
+        run.summary.errors.push(outcome.detail.clone());
+    }
+    run.summary.outcome = outcome;
+    run
+}
 
-        if !check_branch_in(&args.from, &branches) {
+async fn decide(
+    forge: &dyn Forge,
+    repo: &Repository,
+    token: &str,
+    local_git: &LocalGit,
+    args: &PromoteArgs,
+    summary: &mut RepoSummary,
+) -> Decision {
+    let branches: Vec<Branch> = match forge.list_branches(repo).await {
+        Ok(branches) => branches,
+        Err(e) => {
             error!(
-                "Source Branch {} doesn't exist for repo {}",
-                args.from, repo.name
+                "Couldn't get branches for repo {:?}, error: {}. Skipping ...",
+                &repo.name,
+                e.error_message()
             );
+
+            if let Some(extra_info) = e.extra_info() {
+                debug!("{}", extra_info);
+            }
+
+            return Decision::stop(Outcome::failed(format!(
+                "unable to list branches: {}",
+                e.error_message()
+            )));
         }
+    };
+
+    for branch in branches.iter() {
+        debug!("{}, branch: {}", repo.name, branch.name);
+    }
+
+    if !check_branch_in(&args.from, &branches) {
+        error!(
+            "Source Branch {} doesn't exist for repo {}",
+            args.from, repo.name
+        );
+    }
 
-        if args.create_branches {
-            //
-            if check_branch_in(&args.to, &branches) {
-                if !args.create_pulls {
-                    info!(
-                        r#"Destination Branch `{}` already exists for repo `{}`.
+    // nothing is written when a conflict is predicted; a missing `to` is
+    // created from `from` and can't conflict
+    if args.predict_conflicts && check_branch_in(&args.to, &branches) {
+        let prediction = predict_conflicts(local_git, repo, token, args).await;
+        let writes = args.create_branches || args.create_pulls || args.direct || args.back_merge;
+        let predicted_failure = matches!(prediction.status, Status::Conflict | Status::Failed);
+        if predicted_failure || !writes {
+            return Decision::stop(prediction);
+        }
+    }
+
+    if args.create_branches {
+        //
+        if check_branch_in(&args.to, &branches) {
+            if !args.create_pulls {
+                info!(
+                    r#"Destination Branch `{}` already exists for repo `{}`.
                 Use --create-pulls to create pull requests and update it."#,
-                        args.to, repo.name
-                    );
-                }
-            } else {
-                let from_ref = match gh.get_reference(&repo, &args.from).await {
-                    Ok(from_ref) => from_ref,
-                    Err(e) => {
-                        error!(
-                            "Unable to get reference {}: {:?}",
-                            args.from,
-                            e.error_message()
-                        );
-                        continue;
-                    }
-                };
-
-                match gh.create_reference(&repo, &args.to, &from_ref).await {
-                    Ok(_) => {
-                        info!("Branch `{}` created successfully on {}", args.to, repo.name);
-                        // branch newly created, no need to create a pull request
-                        continue;
-                    }
-                    Err(e) => {
-                        error!(
-                            "Error on creating branch `{}` for `{}`: {}",
-                            args.to,
-                            repo.name,
-                            e.error_message()
-                        );
-                    }
-                };
+                    args.to, repo.name
+                );
             }
+        } else {
+            // branch newly created, no need to create a pull request
+            return Decision::act(vec![Action::CreateBranch {
+                branch: args.to.clone(),
+                from: args.from.clone(),
+            }]);
         }
+    }
 
-        if !check_branch_in(&args.to, &branches) {
-            error!(
-                r#"Destination Branch `{}` doesn't exist for repo `{}`.
+    if !check_branch_in(&args.to, &branches) {
+        error!(
+            r#"Destination Branch `{}` doesn't exist for repo `{}`.
                 Use --create-branches to create it or create it manually on gh."#,
-                args.to, repo.name
+            args.to, repo.name
+        );
+        return Decision::stop(Outcome::failed(format!("`{}` doesn't exist", args.to)));
+    }
+
+    if args.back_merge {
+        return plan_back_merge(forge, repo, args, summary).await;
+    }
+
+    if args.direct {
+        return plan_direct(forge, repo, args, summary).await;
+    }
+
+    plan_promotion(forge, repo, args, summary).await
+}
+
+async fn promote_repo(
+    config: &Config,
+    forges: &Forges,
+    local_git: &LocalGit,
+    repo_config: &RepoConfig,
+    args: &PromoteArgs,
+    saved_plan: Option<&RepoPlan>,
+    run: &mut RepoRun,
+) -> Outcome {
+    let (forge, token, repo) = match open_repo(config, forges, repo_config).await {
+        Ok(opened) => opened,
+        Err(outcome) => return outcome,
+    };
+    let checks_timeout = Duration::from_secs(args.checks_timeout);
+
+    if let Some(saved_plan) = saved_plan {
+        info!("Applying the plan for {}", repo.name);
+        let (outcome, pull_request) = apply_actions(
+            forge,
+            &repo,
+            &saved_plan.actions,
+            checks_timeout,
+            None,
+            &mut run.summary,
+        )
+        .await;
+        if let Some(pr) = pull_request {
+            run.summary.pull_number = Some(pr.number);
+            run.summary.pull_url = Some(pr.url);
+        }
+        return outcome;
+    }
+
+    let args = match args.for_repo(&repo, config, repo_config) {
+        Ok(args) => args,
+        Err(e) => {
+            error!("Skipping {}: {}", repo.name, e);
+            return Outcome::new(Status::Skipped, e);
+        }
+    };
+
+    info!("Processing repo: {}", repo.name);
+
+    let mut decision = decide(forge, &repo, token, local_git, &args, &mut run.summary).await;
+    // the stage is tagged once merged into
+    let merges = decision.actions.iter().any(|action| {
+        matches!(
+            action,
+            Action::MergePull { .. } | Action::MergeBranches { .. }
+        )
+    });
+    if let (Some(tag), true) = (&args.tag, merges) {
+        decision.actions.push(Action::TagRelease {
+            tag: tag.clone(),
+            branch: args.to.clone(),
+        });
+    }
+    let (outcome, pull_request) = if decision.actions.is_empty() {
+        (decision.outcome, decision.pull)
+    } else if args.dry_run {
+        let planned = Outcome::new(
+            Status::Planned,
+            format!("{} action(s)", decision.actions.len()),
+        );
+        run.plan = Some(RepoPlan {
+            repo: repo.name.clone(),
+            from: args.from.clone(),
+            to: args.to.clone(),
+            actions: decision.actions,
+        });
+        (planned, decision.pull)
+    } else {
+        apply_actions(
+            forge,
+            &repo,
+            &decision.actions,
+            checks_timeout,
+            decision.pull,
+            &mut run.summary,
+        )
+        .await
+    };
+
+    if let Some(pr) = &pull_request {
+        run.summary.pull_number = Some(pr.number);
+        run.summary.pull_url = Some(pr.url.clone());
+    }
+
+    if args.conflict_report.is_some() {
+        run.conflict_report = conflict_report(
+            forge,
+            &repo,
+            &args,
+            pull_request,
+            &outcome,
+            &mut run.summary,
+        )
+        .await;
+    }
+    outcome
+}
+
+/// The conflict report of a repo: of its pull request when it isn't mergeable,
+/// found even when the run doesn't create pull requests, else of its branches
+/// when they were found to conflict.
+async fn conflict_report(
+    forge: &dyn Forge,
+    repo: &Repository,
+    args: &PromoteArgs,
+    pull_request: Option<PullRequest>,
+    outcome: &Outcome,
+    summary: &mut RepoSummary,
+) -> Option<ConflictReport> {
+    let pull_request = match pull_request {
+        Some(pr) => Some(pr),
+        None if args.back_merge || args.direct => None,
+        // forges without pull requests only have branches to report on
+        None => match forge.find_pull(repo, &args.from, &args.to).await {
+            Ok(pr) => pr,
+            Err(e) => {
+                debug!("No pull request to report on: {}", e.error_message());
+                None
+            }
+        },
+    };
+    let report = match &pull_request {
+        Some(pr) if pr.mergeable == Some(false) => {
+            build_conflict_report(forge, repo, &pr.head, &pr.base, Some(pr)).await
+        }
+        Some(_) => return None,
+        None if outcome.status == Status::Conflict => {
+            build_conflict_report(forge, repo, &args.from, &args.to, None).await
+        }
+        None => return None,
+    };
+    match report {
+        Ok(report) => Some(report),
+        Err(e) => {
+            error!(
+                "Unable to build conflict report for {}: {}",
+                repo.name,
+                e.error_message()
             );
-            continue;
-        }
-
-        let mut pull_request: Option<PullRequest> = None;
-
-        if args.create_pulls {
-            //
-            info!("Comparing {} and {} for PR", args.to, args.from);
-            let comp = match gh.compare_branches(&repo, &args.to, &args.from).await {
-                Ok(comp) => comp,
-                Err(e) => {
-                    error!(
-                        "Unable to get comparison between {} and {} : {}",
-                        args.to,
-                        args.from,
-                        e.error_message()
-                    );
-                    if let Some(extra_info) = e.extra_info() {
-                        debug!("{}", extra_info);
-                    }
-                    continue;
-                }
-            };
+            summary.errors.push(format!(
+                "unable to build the conflict report: {}",
+                e.error_message()
+            ));
+            None
+        }
+    }
+}
 
-            info!("`{}` is {:?} to `{}`", args.to, comp.status, args.from);
-            pull_request = match comp.status {
-                CompareStatus::Behind | CompareStatus::Diverged => {
-                    info!(
-                        "Creating pull request from {} into {} for {}",
-                        args.from, args.to, repo.name
-                    );
-                    get_or_create_pull_request(&gh, &repo, config.org_name.clone(), &args).await
-                }
-                _ => {
-                    info!("Nothing to merge !");
-                    None
+fn build_forges(config: &Config, repos: &[&RepoConfig], global: &GlobalArgs) -> Forges {
+    let mut forges: Forges = HashMap::new();
+    for repo_config in repos.iter() {
+        let key = (
+            config.provider_of(repo_config),
+            config.owner_of(repo_config).to_string(),
+        );
+        if let Entry::Vacant(entry) = forges.entry(key) {
+            let (provider, owner) = entry.key();
+            let forge = build_forge(config, *provider, owner, global);
+            entry.insert(forge);
+        }
+    }
+    forges
+}
+
+async fn promote(config: &Config, global: &GlobalArgs, args: &PromoteArgs) {
+    let saved_plan = match args.apply.as_ref().map(|path| Plan::load(path)) {
+        Some(Ok(plan)) => Some(plan),
+        Some(Err(e)) => {
+            error!("Unable to load the plan {}", e);
+            std::process::exit(exitcode::NOINPUT);
+        }
+        None => None,
+    };
+    // a saved plan is applied to its own repos
+    let repos: Vec<&RepoConfig> = match &saved_plan {
+        Some(plan) => plan
+            .repos
+            .iter()
+            .filter_map(|repo_plan| {
+                let repo_config = config
+                    .repos
+                    .iter()
+                    .find(|repo_config| repo_config.name() == repo_plan.repo);
+                if repo_config.is_none() {
+                    warn!("Skipping {}: not in the config", repo_plan.repo);
                 }
-            };
+                repo_config
+            })
+            .collect(),
+        None => config.repos.iter().collect(),
+    };
+    let plan_of = |repo_config: &RepoConfig| {
+        saved_plan.as_ref().and_then(|plan| {
+            plan.repos
+                .iter()
+                .find(|repo_plan| repo_plan.repo == repo_config.name())
+        })
+    };
+
+    let local_git = LocalGit::new(args.cache_dir.clone());
+    let forges = build_forges(config, &repos, global);
+    let runs: Vec<RepoRun> = for_each_repo(&repos, global.jobs, |repo_config| {
+        process_repo(
+            config,
+            &forges,
+            &local_git,
+            repo_config,
+            args,
+            plan_of(repo_config),
+        )
+    })
+    .await;
+
+    let mut summaries = Vec::with_capacity(runs.len());
+    let mut conflict_reports: Vec<ConflictReport> = vec![];
+    let mut plan = Plan::default();
+    for run in runs {
+        summaries.push(run.summary);
+        conflict_reports.extend(run.conflict_report);
+        plan.repos.extend(run.plan);
+    }
+
+    // with `--output json|csv`, stdout only holds the results
+    let mut stdout = std::io::stdout();
+    let mut stderr = std::io::stderr();
+    let out: &mut dyn Write = match global.output {
+        OutputFormat::Table => &mut stdout,
+        OutputFormat::Json | OutputFormat::Csv => &mut stderr,
+    };
+    if let Some(format) = &args.conflict_report {
+        print_conflict_reports(&conflict_reports, format, out);
+    }
+    if args.dry_run {
+        print_plan(&plan, out);
+        if let Some(path) = &args.save_plan {
+            match plan.save(path) {
+                Ok(_) => info!("Plan saved to {}", path.display()),
+                Err(e) => error!("Unable to save the plan {}", e),
+            }
         }
+    }
+    print_results(&summaries, &global.output);
+    std::process::exit(exit_code(&summaries));
+}
 
-        if args.merge {
-            if let Some(pr) = pull_request {
-                // merge
-                info!("Merging {} for {}", pr.title, repo.name);
-                merge_and_delete(&gh, &pr, &args).await;
-            } else {
-                info!("No pull requests to merge for {}", repo.name);
+#[tokio::main]
+async fn main() {
+    logging::init();
+    let cli = Cli::parse();
+    match &cli.command {
+        Command::Config(ConfigCommand::Schema) => {
+            print!("{}", config::schema());
+            return;
+        }
+        Command::Config(ConfigCommand::Init(args)) => {
+            if let Err(e) = init::run(args, cli.global.config.as_deref()).await {
+                error!("{}", e.message);
+                std::process::exit(e.code);
+            }
+            return;
+        }
+        _ => {}
+    }
+    let pipeline = match &cli.command {
+        Command::Promote(args) => args.pipeline.clone(),
+        _ => None,
+    };
+    let loader = ConfigLoader::new()
+        .with_path(cli.global.config.clone())
+        .with_profile(cli.global.profile.clone())
+        .with_groups(cli.global.group.clone())
+        .with_pipeline(pipeline);
+    let mut config: Config = match loader.load() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(exitcode::CONFIG);
+        }
+    };
+    let (selectors, configured) = if cli.global.select.is_empty() {
+        (std::mem::take(&mut config.select), None)
+    } else {
+        let repos = std::mem::take(&mut config.repos);
+        (cli.global.select.clone(), Some(repos))
+    };
+    if let Err(e) = select::add_selected(&mut config, &selectors, &cli.global).await {
+        error!("{}", e.message());
+        std::process::exit(e.exit_code());
+    }
+    if let Some(configured) = configured {
+        select::keep_configured(&mut config, configured);
+    }
+
+    let global = &cli.global;
+    let repos: Vec<&RepoConfig> = config.repos.iter().collect();
+    match &cli.command {
+        Command::Promote(args) => {
+            if let Err(e) = args.hop(&config) {
+                error!("{}", e);
+                std::process::exit(exitcode::CONFIG);
             }
+            info!("Managing {}", config.org_name);
+            promote(&config, global, args).await;
         }
+        Command::Status(branches) => {
+            let forges = build_forges(&config, &repos, global);
+            let statuses: Vec<RepoStatus> = for_each_repo(&repos, global.jobs, |repo_config| {
+                list_repo(&config, &forges, repo_config, branches)
+            })
+            .await;
+            print_statuses(&statuses, &global.output);
+        }
+        Command::Repo(command) => {
+            let forges = build_forges(&config, &repos, global);
+            let summaries: Vec<RepoSummary> = for_each_repo(&repos, global.jobs, |repo_config| {
+                command_repo(&config, &forges, repo_config, command)
+            })
+            .await;
+            print_results(&summaries, &global.output);
+            std::process::exit(exit_code(&summaries));
+        }
+        Command::Config(ConfigCommand::Show) => match commands::show_config(&config) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                error!("Unable to print the config: {}", e);
+                std::process::exit(exitcode::SOFTWARE);
+            }
+        },
+        Command::Config(ConfigCommand::Schema) => unreachable!("printed before loading"),
+        Command::Config(ConfigCommand::Init(_)) => unreachable!("written before loading"),
     }
 }
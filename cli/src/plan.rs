@@ -0,0 +1,126 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+use config::MergeMethod;
+use serde::{Deserialize, Serialize};
+
+/// A write a run makes on a repo.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    CreateBranch {
+        branch: String,
+        from: String,
+    },
+    OpenPull {
+        from: String,
+        to: String,
+        title: String,
+        body: Option<String>,
+        /// asked to review once the pull request is open
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        reviewers: Vec<String>,
+    },
+    /// brings the pull request up to date with its base, then waits for its checks
+    UpdatePull {
+        number: u64,
+    },
+    /// `None` is the pull request opened earlier in the same plan
+    MergePull {
+        number: Option<u64>,
+        /// the forge's default, a merge commit, when `None`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        method: Option<MergeMethod>,
+    },
+    /// merges `from` into `to` without a pull request
+    MergeBranches {
+        from: String,
+        to: String,
+        message: String,
+    },
+    DeleteBranch {
+        branch: String,
+    },
+    /// publishes a release tagging `branch`, unless `tag` is released
+    TagRelease {
+        tag: String,
+        branch: String,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::CreateBranch { branch, from } => {
+                write!(f, "create branch `{}` from `{}`", branch, from)
+            }
+            Action::OpenPull {
+                from, to, title, ..
+            } => write!(f, "open pull request `{}` into `{}`: {}", from, to, title),
+            Action::UpdatePull { number } => {
+                write!(f, "update #{} with its base and wait for checks", number)
+            }
+            Action::MergePull { number, method } => {
+                match number {
+                    Some(number) => write!(f, "merge #{}", number)?,
+                    None => write!(f, "merge the new pull request")?,
+                }
+                match method {
+                    Some(MergeMethod::Merge) | None => Ok(()),
+                    Some(MergeMethod::Squash) => write!(f, ", squashed"),
+                    Some(MergeMethod::Rebase) => write!(f, ", rebased"),
+                }
+            }
+            Action::MergeBranches { from, to, .. } => {
+                write!(f, "merge `{}` into `{}` without pull request", from, to)
+            }
+            Action::DeleteBranch { branch } => write!(f, "delete branch `{}`", branch),
+            Action::TagRelease { tag, branch } => {
+                write!(f, "release `{}` from `{}`", tag, branch)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RepoPlan {
+    pub repo: String,
+    pub from: String,
+    pub to: String,
+    pub actions: Vec<Action>,
+}
+
+/// The writes a `--dry-run` found to make, `--apply` executes them as they are.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Plan {
+    pub repos: Vec<RepoPlan>,
+}
+
+impl Plan {
+    pub fn load(path: &Path) -> Result<Plan, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+pub fn print_plan(plan: &Plan, out: &mut dyn Write) {
+    let _ = writeln!(out, "Plan:");
+    for repo_plan in plan.repos.iter() {
+        let _ = writeln!(
+            out,
+            "  {} (`{}` into `{}`)",
+            repo_plan.repo, repo_plan.from, repo_plan.to
+        );
+        for action in repo_plan.actions.iter() {
+            let _ = writeln!(out, "    - {}", action);
+        }
+    }
+}
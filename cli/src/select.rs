@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use config::{Config, RepoConfig, RepoOverrides, Selector};
+use forge::{ForgeError, ListedRepo};
+use log::{debug, info};
+use regex::Regex;
+
+use crate::{build_forge, GlobalArgs};
+
+/// Why the repos of a selector could not be listed.
+pub enum SelectError {
+    /// a name glob or regex that doesn't compile
+    Pattern(String),
+    /// the forge failed to list
+    Listing(String),
+}
+
+impl SelectError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SelectError::Pattern(_) => exitcode::CONFIG,
+            SelectError::Listing(_) => exitcode::UNAVAILABLE,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            SelectError::Pattern(message) | SelectError::Listing(message) => message,
+        }
+    }
+}
+
+fn listing_error(owner: &str, e: ForgeError) -> SelectError {
+    if let Some(extra_info) = e.extra_info() {
+        debug!("{extra_info}");
+    }
+    SelectError::Listing(format!(
+        "unable to list the repos of {owner}: {}",
+        e.error_message()
+    ))
+}
+
+/// `glob` as an anchored regex, `*` matching any run of characters and `?`
+/// any single one.
+pub fn glob_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// The name criteria of a selector, compiled.
+struct Patterns {
+    name: Option<Regex>,
+    regex: Option<Regex>,
+}
+
+impl Patterns {
+    fn compile(selector: &Selector) -> Result<Patterns, SelectError> {
+        let compile = |pattern: String, source: &str| {
+            Regex::new(&pattern)
+                .map_err(|e| SelectError::Pattern(format!("invalid pattern `{source}`: {e}")))
+        };
+        Ok(Patterns {
+            name: match &selector.name {
+                Some(glob) => Some(compile(glob_regex(glob), glob)?),
+                None => None,
+            },
+            regex: match &selector.regex {
+                Some(regex) => Some(compile(regex.clone(), regex)?),
+                None => None,
+            },
+        })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.name
+            .iter()
+            .chain(self.regex.iter())
+            .all(|re| re.is_match(name))
+    }
+}
+
+fn matches(
+    selector: &Selector,
+    patterns: &Patterns,
+    team_repos: Option<&HashSet<String>>,
+    repo: &ListedRepo,
+) -> bool {
+    (selector.archived || !repo.archived)
+        && (selector.forks || !repo.fork)
+        && patterns.matches(&repo.name)
+        && selector
+            .topics
+            .iter()
+            .all(|topic| repo.topics.contains(topic))
+        && selector.language.as_ref().is_none_or(|language| {
+            repo.language
+                .as_ref()
+                .is_some_and(|repo_language| repo_language.eq_ignore_ascii_case(language))
+        })
+        && team_repos.is_none_or(|team_repos| team_repos.contains(&repo.name))
+}
+
+/// Names of the repos of its owner `selector` picks, in the order they are
+/// listed, then its `include` ones.
+async fn select(
+    config: &Config,
+    selector: &Selector,
+    owner: &String,
+    global: &GlobalArgs,
+) -> Result<Vec<String>, SelectError> {
+    let patterns = Patterns::compile(selector)?;
+    let provider = config
+        .owners
+        .get(owner)
+        .map(|owner| owner.provider)
+        .unwrap_or_default();
+    let (forge, _) = build_forge(config, provider, owner, global).map_err(SelectError::Listing)?;
+
+    let listed = forge
+        .list_repos()
+        .await
+        .map_err(|e| listing_error(owner, e))?;
+    let team_repos: Option<HashSet<String>> = match &selector.team {
+        Some(team) => Some(
+            forge
+                .list_team_repos(team)
+                .await
+                .map_err(|e| listing_error(owner, e))?
+                .into_iter()
+                .collect(),
+        ),
+        None => None,
+    };
+
+    let mut names: Vec<String> = listed
+        .iter()
+        .filter(|repo| matches(selector, &patterns, team_repos.as_ref(), repo))
+        .map(|repo| repo.name.clone())
+        .collect();
+    for name in &selector.include {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names.retain(|name| !selector.exclude.contains(name));
+    Ok(names)
+}
+
+/// Swaps the selected `config.repos` for their entry in `configured`, the
+/// config's repos, to keep their settings; when the config was narrowed to
+/// groups, the selected repos outside of them are dropped.
+pub fn keep_configured(config: &mut Config, configured: Vec<RepoConfig>) {
+    let selected = config.repos.len();
+    let repos: Vec<RepoConfig> = config
+        .repos
+        .iter()
+        .filter_map(|repo| {
+            let entry = configured.iter().find(|entry| {
+                entry.name() == repo.name() && config.owner_of(entry) == config.owner_of(repo)
+            });
+            match (entry, config.grouped) {
+                (Some(entry), _) => Some(entry.clone()),
+                (None, false) => Some(repo.clone()),
+                (None, true) => None,
+            }
+        })
+        .collect();
+    if config.grouped {
+        info!(
+            "Kept {} of the {} selected repos in the groups",
+            repos.len(),
+            selected
+        );
+    }
+    config.repos = repos;
+}
+
+/// Adds the repos of `selectors` to `config.repos`, skipping the ones
+/// already in it.
+pub async fn add_selected(
+    config: &mut Config,
+    selectors: &[Selector],
+    global: &GlobalArgs,
+) -> Result<(), SelectError> {
+    for selector in selectors {
+        let owner = selector
+            .owner
+            .clone()
+            .unwrap_or_else(|| config.org_name.clone());
+        let names = select(config, selector, &owner, global).await?;
+        info!("Selected {} repos of {}", names.len(), owner);
+        for name in names {
+            let present = config
+                .repos
+                .iter()
+                .any(|repo| repo.name() == name && config.owner_of(repo) == owner);
+            if present {
+                continue;
+            }
+            let repo = if owner == config.org_name {
+                RepoConfig::Name(name)
+            } else {
+                RepoConfig::Repo {
+                    name,
+                    owner: Some(owner.clone()),
+                    provider: None,
+                    overrides: RepoOverrides::default(),
+                }
+            };
+            config.repos.push(repo);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use forge::{Check, CheckState, Forge, Repository, Review, ReviewState};
+use log::{debug, error};
+use serde::Serialize;
+
+use crate::output::{csv_line, print_table, OutputFormat};
+
+/// Where a repo is in the promotion of `from` into `to`.
+#[derive(Serialize, Debug)]
+pub struct RepoStatus {
+    pub repo: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub from_exists: bool,
+    pub to_exists: bool,
+    /// commits on `from` missing from `to`, the ones to promote
+    pub behind_by: Option<u64>,
+    /// commits on `to` missing from `from`, the ones to back-merge
+    pub ahead_by: Option<u64>,
+    pub pull_number: Option<u64>,
+    pub pull_url: Option<String>,
+    pub mergeable_state: Option<String>,
+    pub review: Option<&'static str>,
+    pub checks: Option<&'static str>,
+    pub error: Option<String>,
+}
+
+impl RepoStatus {
+    pub fn new(repo: String) -> RepoStatus {
+        RepoStatus {
+            repo,
+            from: None,
+            to: None,
+            from_exists: false,
+            to_exists: false,
+            behind_by: None,
+            ahead_by: None,
+            pull_number: None,
+            pull_url: None,
+            mergeable_state: None,
+            review: None,
+            checks: None,
+            error: None,
+        }
+    }
+}
+
+/// A change request from anyone wins over approvals.
+fn review_status(states: &[ReviewState]) -> &'static str {
+    if states.contains(&ReviewState::ChangesRequested) {
+        "changes requested"
+    } else if states.contains(&ReviewState::Approved) {
+        "approved"
+    } else if states.contains(&ReviewState::Commented) {
+        "commented"
+    } else {
+        "none"
+    }
+}
+
+fn checks_status(states: &[CheckState]) -> &'static str {
+    if states.contains(&CheckState::Failure) {
+        "failure"
+    } else if states.contains(&CheckState::Pending) {
+        "pending"
+    } else if states.is_empty() {
+        "none"
+    } else {
+        "success"
+    }
+}
+
+/// Where the reviews of a pull request stand: like on github, the last
+/// approval, change request or dismissal of each reviewer counts, comments
+/// don't replace them.
+pub fn pull_review(reviews: Vec<Review>) -> &'static str {
+    let mut last_reviews: HashMap<Option<String>, ReviewState> = HashMap::new();
+    for review in reviews {
+        match review.state {
+            ReviewState::Pending => {}
+            ReviewState::Commented => {
+                last_reviews.entry(review.author).or_insert(review.state);
+            }
+            _ => {
+                last_reviews.insert(review.author, review.state);
+            }
+        }
+    }
+    let states: Vec<ReviewState> = last_reviews.into_values().collect();
+    review_status(&states)
+}
+
+pub fn pull_checks(checks: Vec<Check>) -> &'static str {
+    let states: Vec<CheckState> = checks.into_iter().map(|check| check.state).collect();
+    checks_status(&states)
+}
+
+/// Reads the branches, their comparison and the open pull request of `from`
+/// into `to`, nothing is written.
+pub async fn fill_status(
+    forge: &dyn Forge,
+    repo: &Repository,
+    from: &str,
+    to: &str,
+    status: &mut RepoStatus,
+) -> Result<(), String> {
+    status.from = Some(from.to_string());
+    status.to = Some(to.to_string());
+
+    let branches = forge
+        .list_branches(repo)
+        .await
+        .map_err(|e| format!("unable to list branches: {}", e.error_message()))?;
+    status.from_exists = branches.iter().any(|branch| branch.name == from);
+    status.to_exists = branches.iter().any(|branch| branch.name == to);
+    if !status.from_exists || !status.to_exists {
+        return Ok(());
+    }
+
+    let comp = forge
+        .compare_branches(repo, to, from)
+        .await
+        .map_err(|e| format!("comparison failed: {}", e.error_message()))?;
+    status.behind_by = Some(comp.behind_by);
+    status.ahead_by = Some(comp.ahead_by);
+
+    let pr = match forge.find_pull(repo, from, to).await {
+        Ok(Some(pr)) => pr,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            debug!("No pull requests for {}: {}", repo.name, e.error_message());
+            return Ok(());
+        }
+    };
+    // mergeability is only computed on the pull request itself
+    let pr = forge
+        .get_pull(repo, pr.number)
+        .await
+        .map_err(|e| format!("unable to get #{}: {}", pr.number, e.error_message()))?;
+    status.pull_number = Some(pr.number);
+    status.pull_url = Some(pr.url.clone());
+    status.mergeable_state = pr.mergeable_state.clone().or_else(|| {
+        pr.mergeable
+            .map(|mergeable| String::from(if mergeable { "clean" } else { "dirty" }))
+    });
+
+    let reviews = forge
+        .list_reviews(repo, pr.number)
+        .await
+        .map_err(|e| format!("unable to get reviews: {}", e.error_message()))?;
+    status.review = Some(pull_review(reviews));
+
+    let checks = forge
+        .list_checks(repo, &pr.head_sha)
+        .await
+        .map_err(|e| format!("unable to get checks: {}", e.error_message()))?;
+    status.checks = Some(pull_checks(checks));
+    Ok(())
+}
+
+fn branches_cell(status: &RepoStatus) -> String {
+    match (status.from_exists, status.to_exists) {
+        (true, true) => String::from("ok"),
+        (false, true) => String::from("no from"),
+        (true, false) => String::from("no to"),
+        (false, false) => String::from("-"),
+    }
+}
+
+fn cell<T: ToString>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| String::from("-"))
+}
+
+pub fn print_statuses(statuses: &[RepoStatus], format: &OutputFormat) {
+    match format {
+        OutputFormat::Table => {
+            let mut rows: Vec<[String; 10]> = vec![[
+                String::from("REPO"),
+                String::from("FROM"),
+                String::from("TO"),
+                String::from("BRANCHES"),
+                String::from("BEHIND"),
+                String::from("AHEAD"),
+                String::from("PULL REQUEST"),
+                String::from("MERGEABLE"),
+                String::from("REVIEW"),
+                String::from("CHECKS"),
+            ]];
+            for status in statuses {
+                rows.push([
+                    status.repo.clone(),
+                    cell(&status.from),
+                    cell(&status.to),
+                    status
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| branches_cell(status)),
+                    cell(&status.behind_by),
+                    cell(&status.ahead_by),
+                    cell(&status.pull_number.map(|number| format!("#{}", number))),
+                    cell(&status.mergeable_state),
+                    cell(&status.review),
+                    cell(&status.checks),
+                ]);
+            }
+            print_table(&rows, &mut std::io::stdout());
+        }
+        OutputFormat::Json => match serde_json::to_string_pretty(statuses) {
+            Ok(json) => println!("{}", json),
+            Err(e) => error!("Unable to serialize the statuses: {}", e),
+        },
+        OutputFormat::Csv => {
+            println!("repo,from,to,from_exists,to_exists,behind_by,ahead_by,pull_number,pull_url,mergeable_state,review,checks,error");
+            for status in statuses {
+                let optional = |value: Option<String>| value.unwrap_or_default();
+                println!(
+                    "{}",
+                    csv_line(&[
+                        status.repo.clone(),
+                        optional(status.from.clone()),
+                        optional(status.to.clone()),
+                        status.from_exists.to_string(),
+                        status.to_exists.to_string(),
+                        optional(status.behind_by.map(|count| count.to_string())),
+                        optional(status.ahead_by.map(|count| count.to_string())),
+                        optional(status.pull_number.map(|number| number.to_string())),
+                        optional(status.pull_url.clone()),
+                        optional(status.mergeable_state.clone()),
+                        optional(status.review.map(String::from)),
+                        optional(status.checks.map(String::from)),
+                        optional(status.error.clone()),
+                    ])
+                );
+            }
+        }
+    }
+}
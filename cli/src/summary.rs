@@ -0,0 +1,184 @@
+use std::fmt;
+
+use forge::CompareStatus;
+use serde::{Serialize, Serializer};
+
+use crate::output::{csv_line, OutputFormat};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Done,
+    /// writes found by a dry run
+    Planned,
+    NothingToDo,
+    Skipped,
+    Conflict,
+    /// waiting on github, e.g. mergeability not computed yet
+    Pending,
+    Failed,
+}
+
+impl Status {
+    /// Whether the repo ended somewhere else than asked.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Conflict | Status::Pending | Status::Failed)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match self {
+            Status::Done => "done",
+            Status::Planned => "planned",
+            Status::NothingToDo => "nothing to do",
+            Status::Skipped => "skipped",
+            Status::Conflict => "conflict",
+            Status::Pending => "pending",
+            Status::Failed => "failed",
+        };
+        f.pad(status)
+    }
+}
+
+/// What happened to a repo, for the summary.
+#[derive(Clone, Debug, Serialize)]
+pub struct Outcome {
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Outcome {
+    pub fn new(status: Status, detail: impl Into<String>) -> Outcome {
+        Outcome {
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn done(detail: impl Into<String>) -> Outcome {
+        Outcome::new(Status::Done, detail)
+    }
+
+    pub fn failed(detail: impl Into<String>) -> Outcome {
+        Outcome::new(Status::Failed, detail)
+    }
+}
+
+fn serialize_compare<S: Serializer>(
+    status: &Option<CompareStatus>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    status.as_ref().map(compare_name).serialize(serializer)
+}
+
+pub fn compare_name(status: &CompareStatus) -> &'static str {
+    match status {
+        CompareStatus::Ahead => "ahead",
+        CompareStatus::Behind => "behind",
+        CompareStatus::Diverged => "diverged",
+        CompareStatus::Identical => "identical",
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RepoSummary {
+    pub repo: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    /// status of `to` relative to `from`
+    #[serde(serialize_with = "serialize_compare")]
+    pub compare: Option<CompareStatus>,
+    pub pull_number: Option<u64>,
+    pub pull_url: Option<String>,
+    pub merged_sha: Option<String>,
+    pub branch_deleted: bool,
+    pub errors: Vec<String>,
+}
+
+impl RepoSummary {
+    pub fn new(repo: String) -> RepoSummary {
+        RepoSummary {
+            repo,
+            outcome: Outcome::new(Status::NothingToDo, ""),
+            compare: None,
+            pull_number: None,
+            pull_url: None,
+            merged_sha: None,
+            branch_deleted: false,
+            errors: vec![],
+        }
+    }
+}
+
+pub fn print_results(summaries: &[RepoSummary], format: &OutputFormat) {
+    match format {
+        OutputFormat::Table => print_summary(summaries),
+        OutputFormat::Json => match serde_json::to_string_pretty(summaries) {
+            Ok(json) => println!("{}", json),
+            Err(e) => log::error!("Unable to serialize the results: {}", e),
+        },
+        OutputFormat::Csv => print_csv(summaries),
+    }
+}
+
+/// Prints one line per repo, in the order of the config.
+fn print_summary(summaries: &[RepoSummary]) {
+    let width = summaries
+        .iter()
+        .map(|summary| summary.repo.len())
+        .max()
+        .unwrap_or(0);
+    println!("Summary:");
+    for summary in summaries {
+        println!(
+            "  {:<width$}  {:<13}  {}",
+            summary.repo, summary.outcome.status, summary.outcome.detail
+        );
+    }
+}
+
+fn print_csv(summaries: &[RepoSummary]) {
+    println!("repo,status,detail,compare,pull_number,pull_url,merged_sha,branch_deleted,errors");
+    for summary in summaries {
+        let fields = [
+            summary.repo.clone(),
+            serde_json::to_value(summary.outcome.status)
+                .ok()
+                .and_then(|status| status.as_str().map(String::from))
+                .unwrap_or_default(),
+            summary.outcome.detail.clone(),
+            summary
+                .compare
+                .as_ref()
+                .map(compare_name)
+                .unwrap_or_default()
+                .to_string(),
+            summary
+                .pull_number
+                .map(|number| number.to_string())
+                .unwrap_or_default(),
+            summary.pull_url.clone().unwrap_or_default(),
+            summary.merged_sha.clone().unwrap_or_default(),
+            summary.branch_deleted.to_string(),
+            summary.errors.join("; "),
+        ];
+        println!("{}", csv_line(&fields));
+    }
+}
+
+/// `OK` when every repo went through, `TEMPFAIL` when some of them failed,
+/// `UNAVAILABLE` when all of them did.
+pub fn exit_code(summaries: &[RepoSummary]) -> exitcode::ExitCode {
+    let failures = summaries
+        .iter()
+        .filter(|summary| summary.outcome.status.is_failure())
+        .count();
+    if failures == 0 {
+        exitcode::OK
+    } else if failures < summaries.len() {
+        exitcode::TEMPFAIL
+    } else {
+        exitcode::UNAVAILABLE
+    }
+}
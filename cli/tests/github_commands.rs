@@ -0,0 +1,434 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use fake_github::{FakeGithub, Fixture};
+use tempfile::TempDir;
+
+fn start_fake() -> FakeGithub {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    FakeGithub::start(Fixture::load(&fixture).unwrap())
+}
+
+fn cli(workdir: &Path, fake: &FakeGithub, args: &[&str]) -> Output {
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "web", "legacy"],
+        "github_api_url": fake.url(),
+    });
+    cli_with_config(workdir, &config, args)
+}
+
+fn cli_with_config(workdir: &Path, config: &serde_json::Value, args: &[&str]) -> Output {
+    std::fs::write(workdir.join("config.json"), config.to_string()).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs a command with `--output json` and returns the status of each repo.
+fn statuses(workdir: &Path, fake: &FakeGithub, args: &[&str]) -> (Option<i32>, Vec<String>) {
+    let mut args = args.to_vec();
+    args.extend(["--output", "json"]);
+    let output = cli(workdir, fake, &args);
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let statuses = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["status"].as_str().unwrap().to_string())
+        .collect();
+    (output.status.code(), statuses)
+}
+
+#[test]
+fn branches_are_created_and_deleted() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let (code, result) = statuses(workdir.path(), &fake, &["branch", "create", "release"]);
+    assert_eq!(code, Some(exitcode::OK));
+    assert_eq!(result, ["done", "done", "skipped"]);
+    assert_eq!(fake.branches("api")["release"], vec!["a1", "a2"]);
+
+    let (_, result) = statuses(workdir.path(), &fake, &["branch", "create", "release"]);
+    assert_eq!(result, ["nothing_to_do", "nothing_to_do", "skipped"]);
+
+    let (code, result) = statuses(workdir.path(), &fake, &["branch", "delete", "release"]);
+    assert_eq!(code, Some(exitcode::OK));
+    assert_eq!(result, ["done", "done", "skipped"]);
+    assert!(!fake.branches("web").contains_key("release"));
+
+    // the default branch is never deleted
+    let (_, result) = statuses(workdir.path(), &fake, &["branch", "delete", "main"]);
+    assert_eq!(result, ["skipped", "skipped", "skipped"]);
+    assert!(fake.branches("api").contains_key("main"));
+}
+
+#[test]
+fn pull_requests_are_opened_listed_merged_and_closed() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let (code, result) = statuses(
+        workdir.path(),
+        &fake,
+        &["pr", "open", "--to", "prod", "--reference", "1"],
+    );
+    assert_eq!(code, Some(exitcode::OK));
+    assert_eq!(result, ["done", "done", "skipped"]);
+    assert_eq!(fake.pulls("api")[0].title, "PR for: 1. main into prod");
+
+    let output = cli(
+        workdir.path(),
+        &fake,
+        &["pr", "list", "--to", "prod", "--output", "json"],
+    );
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["pull_number"], 1);
+    assert_eq!(results[1]["pull_number"], 1);
+    assert!(results[1]["detail"].as_str().unwrap().contains("dirty"));
+
+    // web conflicts, its pull request stays open
+    let (code, result) = statuses(workdir.path(), &fake, &["pr", "merge", "--to", "prod"]);
+    assert_eq!(code, Some(exitcode::TEMPFAIL));
+    assert_eq!(result, ["done", "conflict", "skipped"]);
+    assert!(fake.pulls("api")[0].merged);
+
+    let (code, result) = statuses(workdir.path(), &fake, &["pr", "close", "--to", "prod"]);
+    assert_eq!(code, Some(exitcode::OK));
+    assert_eq!(result, ["nothing_to_do", "done", "skipped"]);
+    let web_pull = &fake.pulls("web")[0];
+    assert!(!web_pull.open);
+    assert!(!web_pull.merged);
+}
+
+#[test]
+fn releases_are_published_once() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let (code, result) = statuses(
+        workdir.path(),
+        &fake,
+        &["release", "v1.0", "--branch", "prod"],
+    );
+    assert_eq!(code, Some(exitcode::OK));
+    assert_eq!(result, ["done", "done", "skipped"]);
+    let releases = fake.releases("api");
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].tag, "v1.0");
+    assert_eq!(releases[0].sha, "a1");
+
+    let (_, result) = statuses(workdir.path(), &fake, &["release", "v1.0"]);
+    assert_eq!(result, ["nothing_to_do", "nothing_to_do", "skipped"]);
+    assert_eq!(fake.releases("api").len(), 1);
+}
+
+#[test]
+fn config_is_shown_without_its_tokens() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = cli(workdir.path(), &fake, &["config", "show"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("fake-token"));
+    let config: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["token"], "***");
+    assert_eq!(config["repos"][1], "web");
+}
+
+/// The repos a command with `--select` works on.
+fn selected(workdir: &Path, fake: &FakeGithub, selector: &str) -> Vec<String> {
+    let output = cli(
+        workdir,
+        fake,
+        &[
+            "pr", "list", "--to", "prod", "--select", selector, "--output", "json",
+        ],
+    );
+    repos_of(&output)
+}
+
+/// The repos of the results printed with `--output json`.
+fn repos_of(output: &Output) -> Vec<String> {
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["repo"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn repos_are_selected_among_the_listed_ones() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    // archived repos and forks are left out unless asked for
+    assert_eq!(selected(workdir.path(), &fake, "name=*"), ["api", "web"]);
+    assert_eq!(
+        selected(workdir.path(), &fake, "name=api*,forks"),
+        ["api", "api-fork"]
+    );
+    assert_eq!(
+        selected(workdir.path(), &fake, "topic=backend,language=rust"),
+        ["api"]
+    );
+    assert_eq!(
+        selected(workdir.path(), &fake, "regex=^(web|legacy)$,archived"),
+        ["legacy", "web"]
+    );
+    assert_eq!(
+        selected(
+            workdir.path(),
+            &fake,
+            "team=platform,exclude=api,include=api-fork"
+        ),
+        ["web", "api-fork"]
+    );
+
+    let output = cli(
+        workdir.path(),
+        &fake,
+        &["pr", "list", "--to", "prod", "--select", "regex=("],
+    );
+    assert_eq!(output.status.code(), Some(exitcode::CONFIG));
+    let output = cli(
+        workdir.path(),
+        &fake,
+        &["pr", "list", "--to", "prod", "--select", "team=nobody"],
+    );
+    assert_eq!(output.status.code(), Some(exitcode::UNAVAILABLE));
+}
+
+#[test]
+fn private_repos_of_the_user_are_selected() {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    let mut fixture = Fixture::load(&fixture).unwrap();
+    fixture.owner = String::from("octocat");
+    fixture.repos.get_mut("web").unwrap().private = true;
+    let fake = FakeGithub::start(fixture);
+    let workdir = TempDir::new().unwrap();
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "octocat",
+        "is_user": true,
+        "repos": [],
+        "github_api_url": fake.url(),
+    });
+
+    let output = cli_with_config(
+        workdir.path(),
+        &config,
+        &[
+            "pr", "list", "--to", "prod", "--select", "name=*", "--output", "json",
+        ],
+    );
+    assert_eq!(repos_of(&output), ["api", "web"]);
+}
+
+#[test]
+fn selected_repos_are_kept_to_the_groups() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "web", "legacy"],
+        "groups": {"backend": ["api", "legacy"]},
+        "github_api_url": fake.url(),
+    });
+
+    let output = cli_with_config(
+        workdir.path(),
+        &config,
+        &[
+            "pr", "list", "--to", "prod", "--group", "backend", "--select", "name=*", "--output",
+            "json",
+        ],
+    );
+    assert_eq!(repos_of(&output), ["api"]);
+}
+
+#[test]
+fn repos_of_several_owners_are_managed_in_one_run() {
+    let fake = start_fake();
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    let mut other_fixture = Fixture::load(&fixture).unwrap();
+    other_fixture.owner = String::from("acme");
+    let other = FakeGithub::start(other_fixture);
+    let workdir = TempDir::new().unwrap();
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "acme/web"],
+        "github_api_url": fake.url(),
+        "owners": {
+            "acme": {"url": other.url(), "token_source": {"from": "env", "var": "ACME_TOKEN"}}
+        },
+    });
+
+    std::fs::write(workdir.path().join("config.json"), config.to_string()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir.path())
+        .env("ACME_TOKEN", "acme-token")
+        .args(["branch", "create", "release"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(exitcode::OK));
+    assert!(fake.branches("api").contains_key("release"));
+    assert!(!fake.branches("web").contains_key("release"));
+    assert!(other.branches("web").contains_key("release"));
+    assert!(!other.branches("api").contains_key("release"));
+
+    // the owner's token has to be readable
+    let output = cli_with_config(workdir.path(), &config, &["branch", "create", "release"]);
+    assert_eq!(output.status.code(), Some(exitcode::CONFIG));
+}
+
+fn init(workdir: &Path, fake: &FakeGithub, args: &[&str], answers: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir)
+        .env("MULTIGIT_TOKEN", "fake-token")
+        .args(["config", "init", "--github-api-url", fake.url()])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(answers.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn written_config(workdir: &Path) -> serde_json::Value {
+    let text = std::fs::read_to_string(workdir.join("config.json")).unwrap();
+    serde_json::from_str(&text).unwrap()
+}
+
+#[test]
+fn config_init_writes_the_repos_picked() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    // archived repos and forks are not offered
+    let output = init(
+        workdir.path(),
+        &fake,
+        &["--owner", "rednaks", "--repos", "*"],
+        "",
+    );
+    assert!(output.status.success());
+    let config = written_config(workdir.path());
+    assert_eq!(config["org_name"], "rednaks");
+    assert_eq!(config["is_user"], false);
+    assert_eq!(config["repos"], serde_json::json!(["api", "web"]));
+    assert_eq!(
+        config["token_source"],
+        serde_json::json!({"from": "env", "var": "MULTIGIT_TOKEN"})
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir.path())
+        .env("MULTIGIT_TOKEN", "fake-token")
+        .args(["pr", "list", "--to", "prod"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = init(
+        workdir.path(),
+        &fake,
+        &["--owner", "rednaks", "--repos", "w*"],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(exitcode::CANTCREAT));
+
+    // the user is listed first, then their organizations
+    let output = init(workdir.path(), &fake, &["--force"], "2\nweb, 1\n");
+    assert!(output.status.success());
+    let config = written_config(workdir.path());
+    assert_eq!(config["org_name"], "rednaks");
+    assert_eq!(config["repos"], serde_json::json!(["api", "web"]));
+    let output = init(workdir.path(), &fake, &["--force"], "rednaks\n");
+    assert_eq!(output.status.code(), Some(exitcode::USAGE));
+}
+
+#[test]
+fn config_init_writes_the_format_of_the_extension() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    for file in ["config.toml", "config.yaml"] {
+        let output = init(
+            workdir.path(),
+            &fake,
+            &["--config", file, "--owner", "rednaks", "--repos", "api"],
+            "",
+        );
+        assert!(output.status.success());
+        let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+            .current_dir(workdir.path())
+            .env("MULTIGIT_TOKEN", "fake-token")
+            .args([
+                "--config", file, "pr", "list", "--to", "prod", "--output", "json",
+            ])
+            .output()
+            .unwrap();
+        assert_eq!(repos_of(&output), ["api"]);
+    }
+    let text = std::fs::read_to_string(workdir.path().join("config.toml")).unwrap();
+    assert!(text.contains("org_name = \"rednaks\""));
+
+    let output = init(
+        workdir.path(),
+        &fake,
+        &[
+            "--config",
+            "config.txt",
+            "--owner",
+            "rednaks",
+            "--repos",
+            "api",
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(exitcode::USAGE));
+    assert!(!workdir.path().join("config.txt").exists());
+}
+
+#[test]
+fn config_init_offers_the_private_repos_of_the_user() {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    let mut fixture = Fixture::load(&fixture).unwrap();
+    fixture.owner = String::from("octocat");
+    fixture.repos.get_mut("web").unwrap().private = true;
+    let fake = FakeGithub::start(fixture);
+    let workdir = TempDir::new().unwrap();
+
+    let output = init(workdir.path(), &fake, &["--repos", "*"], "1\n");
+    assert!(output.status.success());
+    let config = written_config(workdir.path());
+    assert_eq!(config["org_name"], "octocat");
+    assert_eq!(config["is_user"], true);
+    assert_eq!(config["repos"], serde_json::json!(["api", "web"]));
+}
@@ -0,0 +1,519 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use fake_github::{FakeGithub, Fixture};
+use tempfile::TempDir;
+
+fn start_fake() -> FakeGithub {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    FakeGithub::start(Fixture::load(&fixture).unwrap())
+}
+
+fn cli(workdir: &Path, fake: &FakeGithub, args: &[&str]) -> Output {
+    let repos = serde_json::json!(["api", "web", "legacy"]);
+    cli_with_repos(workdir, fake, repos, args)
+}
+
+fn cli_with_repos(
+    workdir: &Path,
+    fake: &FakeGithub,
+    repos: serde_json::Value,
+    args: &[&str],
+) -> Output {
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": repos,
+        "github_api_url": fake.url(),
+        "branches": {"dev": "main", "prod": "prod"},
+    });
+    std::fs::write(workdir.join("config.json"), config.to_string()).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn run_cli(workdir: &Path, fake: &FakeGithub, to: &str, extra_args: &[&str]) -> Output {
+    // `--from` defaults to the default branch, `main`
+    let mut args = vec!["promote", "--to", to, "--reference", "1"];
+    args.extend(extra_args);
+    cli(workdir, fake, &args)
+}
+
+/// Branches `release` off the default branch of every repo.
+fn create_release_branches(workdir: &Path, fake: &FakeGithub) {
+    let output = cli(workdir, fake, &["branch", "create", "release"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn pulls_are_created_merged_and_their_branches_deleted() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+    create_release_branches(workdir.path(), &fake);
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &[
+            "--from",
+            "release",
+            "--create-pulls",
+            "--merge",
+            "--delete-branches",
+        ],
+    );
+    // web conflicts
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+
+    let api_pulls = fake.pulls("api");
+    assert_eq!(api_pulls.len(), 1);
+    assert_eq!(api_pulls[0].title, "PR for: 1. release into prod");
+    assert!(api_pulls[0].merged);
+    let api = fake.branches("api");
+    assert!(!api.contains_key("release"));
+    assert!(api.contains_key("main"));
+    let prod = &api["prod"];
+    assert!(prod.contains(&String::from("a2")));
+    assert_eq!(api_pulls[0].merge_commit_sha.as_ref(), prod.last());
+
+    // main and prod both touch index.html
+    let web_pulls = fake.pulls("web");
+    assert_eq!(web_pulls.len(), 1);
+    assert!(web_pulls[0].open);
+    assert!(!web_pulls[0].merged);
+    let web = fake.branches("web");
+    assert!(web.contains_key("release"));
+    assert_eq!(web["prod"], vec!["b1", "b3"]);
+
+    // archived
+    assert!(fake.pulls("legacy").is_empty());
+    assert_eq!(fake.branches("legacy")["prod"], vec!["c1"]);
+}
+
+#[test]
+fn repos_override_branches_and_options() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+    let repos = serde_json::json!([
+        {"name": "api", "merge_method": "squash", "reviewers": ["alice"]},
+        {"name": "web", "branches": {"prod": "staging"}, "create_branches": true},
+    ]);
+
+    let output = cli_with_repos(
+        workdir.path(),
+        &fake,
+        repos,
+        &[
+            "promote",
+            "--from",
+            "@dev",
+            "--to",
+            "@prod",
+            "--reference",
+            "1",
+            "--create-pulls",
+            "--merge",
+        ],
+    );
+    assert!(output.status.success());
+
+    let api_pulls = fake.pulls("api");
+    assert_eq!(api_pulls.len(), 1);
+    assert_eq!(api_pulls[0].base, "prod");
+    assert_eq!(api_pulls[0].requested_reviewers, ["alice"]);
+    assert_eq!(api_pulls[0].merge_method.as_deref(), Some("squash"));
+
+    // `@prod` is `staging` on web, created as the repo allows it
+    assert!(fake.pulls("web").is_empty());
+    let web = fake.branches("web");
+    assert_eq!(web["staging"], web["main"]);
+}
+
+#[test]
+fn selected_repos_keep_their_overrides() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+    let repos = serde_json::json!([
+        "api",
+        {"name": "web", "branches": {"prod": "staging"}, "create_branches": true},
+    ]);
+
+    let output = cli_with_repos(
+        workdir.path(),
+        &fake,
+        repos,
+        &[
+            "promote",
+            "--select",
+            "name=web",
+            "--from",
+            "@dev",
+            "--to",
+            "@prod",
+            "--reference",
+            "1",
+        ],
+    );
+    assert!(output.status.success());
+
+    let web = fake.branches("web");
+    assert_eq!(web["staging"], web["main"]);
+    assert!(!fake.branches("api").contains_key("staging"));
+}
+
+#[test]
+fn existing_pulls_are_reused() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(workdir.path(), &fake, "prod", &["--create-pulls"]);
+    assert!(output.status.success());
+    run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &["--create-pulls", "--merge"],
+    );
+
+    let api_pulls = fake.pulls("api");
+    assert_eq!(api_pulls.len(), 1);
+    assert!(api_pulls[0].merged);
+    assert_eq!(fake.pulls("web").len(), 1);
+}
+
+#[test]
+fn conflicts_are_reported_without_creating_pulls() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    run_cli(workdir.path(), &fake, "prod", &["--create-pulls"]);
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &["--dry-run", "--conflict-report", "table"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.contains("index.html"))
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert!(rows[0].starts_with("web"));
+    assert!(rows[0].contains("/pull/1"));
+
+    // no pull request for a direct merge, its branches are reported
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &["--direct", "--conflict-report", "table"],
+    );
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout
+        .lines()
+        .any(|line| line.starts_with("web") && line.contains("main into prod")));
+}
+
+#[test]
+fn missing_destination_branches_are_created() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(workdir.path(), &fake, "staging", &["--create-branches"]);
+    assert!(output.status.success());
+
+    for repo in ["api", "web"] {
+        let branches = fake.branches(repo);
+        assert_eq!(branches["staging"], branches["main"]);
+    }
+}
+
+#[test]
+fn direct_promotion_skips_conflicting_repos() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(workdir.path(), &fake, "prod", &["--direct"]);
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+
+    let api = fake.branches("api");
+    assert!(api["prod"].contains(&String::from("a2")));
+    assert_eq!(api["prod"].len(), 3);
+    assert_eq!(fake.branches("web")["prod"], vec!["b1", "b3"]);
+    assert!(fake.pulls("api").is_empty());
+}
+
+#[test]
+fn concurrent_runs_keep_logs_grouped_and_the_summary_ordered() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &["--create-pulls", "--jobs", "3"],
+    );
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let api_group = stderr.split("== api ==").nth(1).unwrap();
+    let api_group = api_group.split("\n== ").next().unwrap();
+    assert!(api_group.contains("Processing repo: api"));
+    assert!(!api_group.contains("Processing repo: web"));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let summary: Vec<Vec<&str>> = stdout
+        .split("Summary:\n")
+        .nth(1)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            line.split("  ")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .collect();
+    assert_eq!(summary[0][..2], ["api", "done"]);
+    assert_eq!(summary[1][..2], ["web", "done"]);
+    assert_eq!(summary[2][..2], ["legacy", "skipped"]);
+    assert_eq!(fake.pulls("api").len(), 1);
+    assert_eq!(fake.pulls("web").len(), 1);
+}
+
+#[test]
+fn dry_runs_write_nothing_and_their_plan_is_applied_as_is() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &[
+            "--create-pulls",
+            "--merge",
+            "--delete-branches",
+            "--dry-run",
+            "--save-plan",
+            "plan.json",
+        ],
+    );
+    assert!(output.status.success());
+
+    assert!(fake.pulls("api").is_empty());
+    assert!(fake.pulls("web").is_empty());
+    assert_eq!(fake.branches("api")["prod"], vec!["a1"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("open pull request `main` into `prod`"));
+
+    let plan: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(workdir.path().join("plan.json")).unwrap()).unwrap();
+    let repos = plan["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["repo"], "api");
+    let actions: Vec<&str> = repos[0]["actions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|action| action["action"].as_str().unwrap())
+        .collect();
+    // `main`, the default branch, is never deleted
+    assert_eq!(actions, ["open_pull", "merge_pull"]);
+
+    let output = cli(workdir.path(), &fake, &["promote", "--apply", "plan.json"]);
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+
+    let api_pulls = fake.pulls("api");
+    assert_eq!(api_pulls.len(), 1);
+    assert!(api_pulls[0].merged);
+    assert!(fake.branches("api").contains_key("main"));
+    // the merge stops at the conflict, main is kept
+    let web_pulls = fake.pulls("web");
+    assert_eq!(web_pulls.len(), 1);
+    assert!(!web_pulls[0].merged);
+    assert!(fake.branches("web").contains_key("main"));
+}
+
+#[test]
+fn default_branches_survive_a_plan_deleting_them() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &[
+            "--create-pulls",
+            "--merge",
+            "--dry-run",
+            "--save-plan",
+            "plan.json",
+        ],
+    );
+    assert!(output.status.success());
+    let path = workdir.path().join("plan.json");
+    let mut plan: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    plan["repos"][0]["actions"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({"action": "delete_branch", "branch": "main"}));
+    std::fs::write(&path, plan.to_string()).unwrap();
+
+    cli(workdir.path(), &fake, &["promote", "--apply", "plan.json"]);
+    // github refuses, like the plan would have
+    assert!(fake.pulls("api")[0].merged);
+    assert!(fake.branches("api").contains_key("main"));
+}
+
+#[test]
+fn json_output_lists_each_repo_result() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+    create_release_branches(workdir.path(), &fake);
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &[
+            "--from",
+            "release",
+            "--create-pulls",
+            "--merge",
+            "--delete-branches",
+            "--output",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let api = &results[0];
+    assert_eq!(api["repo"], "api");
+    assert_eq!(api["status"], "done");
+    assert_eq!(api["compare"], "behind");
+    assert_eq!(api["pull_number"], 1);
+    let merged_sha = fake.branches("api")["prod"].last().cloned();
+    assert_eq!(api["merged_sha"].as_str(), merged_sha.as_deref());
+    assert_eq!(api["branch_deleted"], true);
+    let web = &results[1];
+    assert_eq!(web["status"], "conflict");
+    assert_eq!(web["compare"], "diverged");
+    assert_eq!(web["branch_deleted"], false);
+    assert_eq!(results[2]["status"], "skipped");
+}
+
+#[test]
+fn csv_output_has_a_row_per_repo() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = run_cli(
+        workdir.path(),
+        &fake,
+        "prod",
+        &["--create-pulls", "--output", "csv"],
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("repo,status,detail,compare"));
+    assert!(lines[1].starts_with("api,done,"));
+    assert!(lines[3].starts_with("legacy,skipped,archived,"));
+}
+
+#[test]
+fn pipelines_promote_into_a_stage_with_its_rules() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/status.json");
+    let fake = FakeGithub::start(Fixture::load(&fixture).unwrap());
+    let workdir = TempDir::new().unwrap();
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "web", "docs"],
+        "github_api_url": fake.url(),
+        "groups": {"backend": ["api", "web"]},
+        "pipelines": {
+            "release": {
+                "groups": ["backend"],
+                "stages": [
+                    {"name": "dev", "branch": "main"},
+                    {
+                        "name": "prod",
+                        "branch": "prod",
+                        "merge_method": "squash",
+                        "require_approval": true,
+                        "require_checks": true,
+                        "tag": "prod-{date}"
+                    }
+                ]
+            }
+        },
+    });
+    std::fs::write(workdir.path().join("config.json"), config.to_string()).unwrap();
+    let promote = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cli"))
+            .current_dir(workdir.path())
+            .arg("promote")
+            .args(args)
+            .args(["--output", "json"])
+            .output()
+            .unwrap()
+    };
+
+    let output = promote(&["release", "--stage", "dev"]);
+    assert_eq!(output.status.code(), Some(exitcode::CONFIG));
+    let output = promote(&["nightly", "--stage", "prod"]);
+    assert_eq!(output.status.code(), Some(exitcode::CONFIG));
+
+    let output = promote(&["release", "--stage", "prod"]);
+    assert_eq!(output.status.code(), Some(exitcode::TEMPFAIL));
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // docs is not in the pipeline's group
+    let statuses: Vec<(&str, &str)> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| {
+            (
+                result["repo"].as_str().unwrap(),
+                result["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(statuses, [("api", "done"), ("web", "pending")]);
+
+    // approved with passing checks, merged the stage's way and tagged
+    let api_pulls = fake.pulls("api");
+    assert!(api_pulls[0].merged);
+    assert_eq!(api_pulls[0].merge_method.as_deref(), Some("squash"));
+    let releases = fake.releases("api");
+    assert_eq!(releases.len(), 1);
+    assert!(
+        releases[0].tag.starts_with("prod-20"),
+        "{}",
+        releases[0].tag
+    );
+    assert_eq!(Some(&releases[0].sha), fake.branches("api")["prod"].last());
+
+    // changes were requested
+    assert!(!fake.pulls("web")[0].merged);
+    assert!(fake.releases("web").is_empty());
+}
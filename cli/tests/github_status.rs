@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use fake_github::{FakeGithub, Fixture};
+use tempfile::TempDir;
+
+fn start_fake() -> FakeGithub {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/status.json");
+    FakeGithub::start(Fixture::load(&fixture).unwrap())
+}
+
+fn status(workdir: &Path, fake: &FakeGithub, extra_args: &[&str]) -> Output {
+    let config = serde_json::json!({
+        "token": "fake-token",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "web", "docs"],
+        "github_api_url": fake.url(),
+    });
+    std::fs::write(workdir.join("config.json"), config.to_string()).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir)
+        .args(["status", "--to", "prod"])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    output
+}
+
+#[test]
+fn status_reports_where_each_repo_is() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = status(workdir.path(), &fake, &["--output", "json"]);
+
+    let statuses: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let api = &statuses[0];
+    assert_eq!(api["repo"], "api");
+    assert_eq!(api["from"], "main");
+    assert_eq!(api["behind_by"], 1);
+    assert_eq!(api["ahead_by"], 0);
+    assert_eq!(api["pull_number"], 1);
+    assert_eq!(api["mergeable_state"], "clean");
+    // a later comment doesn't take the approval back
+    assert_eq!(api["review"], "approved");
+    assert_eq!(api["checks"], "success");
+
+    let web = &statuses[1];
+    assert_eq!(web["ahead_by"], 1);
+    assert_eq!(web["mergeable_state"], "dirty");
+    assert_eq!(web["review"], "changes requested");
+    assert_eq!(web["checks"], "pending");
+
+    let docs = &statuses[2];
+    assert_eq!(docs["from_exists"], true);
+    assert_eq!(docs["to_exists"], false);
+    assert_eq!(docs["pull_number"], serde_json::Value::Null);
+
+    // nothing was written
+    assert_eq!(fake.pulls("api").len(), 1);
+    assert_eq!(fake.branches("docs").len(), 1);
+}
+
+#[test]
+fn status_prints_a_table_by_default() {
+    let fake = start_fake();
+    let workdir = TempDir::new().unwrap();
+
+    let output = status(workdir.path(), &fake, &[]);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(lines[0][..3], ["REPO", "FROM", "TO"]);
+    assert_eq!(
+        lines[1],
+        ["api", "main", "prod", "ok", "1", "0", "#1", "clean", "approved", "success"]
+    );
+    assert_eq!(lines[3][..4], ["docs", "main", "prod", "no"]);
+}
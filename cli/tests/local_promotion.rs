@@ -0,0 +1,170 @@
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+use git2::{Oid, Repository, Signature};
+use tempfile::TempDir;
+
+fn commit_files(
+    repo: &Repository,
+    branch: &str,
+    parent: Option<Oid>,
+    files: &[(&str, &str)],
+) -> Oid {
+    let parent = parent.map(|oid| repo.find_commit(oid).unwrap());
+    let mut tree_builder = repo
+        .treebuilder(parent.as_ref().map(|c| c.tree().unwrap()).as_ref())
+        .unwrap();
+    for (path, content) in files {
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        tree_builder.insert(path, blob, 0o100644).unwrap();
+    }
+    let tree = repo.find_tree(tree_builder.write().unwrap()).unwrap();
+    let signature = Signature::now("multigit", "multigit@example.com").unwrap();
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(
+        Some(&format!("refs/heads/{}", branch)),
+        &signature,
+        &signature,
+        "commit",
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
+
+fn branch_tip(repo: &Repository, branch: &str) -> Option<Oid> {
+    repo.find_reference(&format!("refs/heads/{}", branch))
+        .ok()
+        .and_then(|reference| reference.target())
+}
+
+fn run_cli(workdir: &Path, root: &Path, repos: &[&str], extra_args: &[&str]) -> ExitStatus {
+    let config = serde_json::json!({
+        "token": "",
+        "org_name": "local",
+        "is_user": true,
+        "repos": repos,
+    });
+    std::fs::write(workdir.join("config.json"), config.to_string()).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(workdir)
+        .args([
+            "promote",
+            "--from",
+            "dev",
+            "--to",
+            "main",
+            "--reference",
+            "1",
+            "--local",
+        ])
+        .arg(root)
+        .args(extra_args)
+        .status()
+        .unwrap()
+}
+
+#[test]
+fn direct_promotion_merges_source_into_destination() {
+    let root = TempDir::new().unwrap();
+    let workdir = TempDir::new().unwrap();
+
+    let repo = Repository::init_bare(root.path().join("api.git")).unwrap();
+    let base = commit_files(&repo, "main", None, &[("README.md", "api\n")]);
+    let dev = commit_files(&repo, "dev", Some(base), &[("feature.rs", "")]);
+    commit_files(&repo, "main", Some(base), &[("hotfix.rs", "")]);
+
+    let status = run_cli(
+        workdir.path(),
+        root.path(),
+        &["api"],
+        &["--direct", "--delete-branches"],
+    );
+    assert!(status.success());
+
+    let main = repo
+        .find_commit(branch_tip(&repo, "main").unwrap())
+        .unwrap();
+    assert_eq!(main.parent_count(), 2);
+    assert!(repo.graph_descendant_of(main.id(), dev).unwrap());
+    assert!(main.tree().unwrap().get_name("feature.rs").is_some());
+    assert!(main.tree().unwrap().get_name("hotfix.rs").is_some());
+    assert_eq!(branch_tip(&repo, "dev"), None);
+}
+
+#[test]
+fn conflicting_direct_promotion_leaves_branches_untouched() {
+    let root = TempDir::new().unwrap();
+    let workdir = TempDir::new().unwrap();
+
+    let repo = Repository::init_bare(root.path().join("api.git")).unwrap();
+    let base = commit_files(&repo, "main", None, &[("README.md", "api\n")]);
+    let dev = commit_files(&repo, "dev", Some(base), &[("README.md", "feature\n")]);
+    let main = commit_files(&repo, "main", Some(base), &[("README.md", "hotfix\n")]);
+
+    let status = run_cli(
+        workdir.path(),
+        root.path(),
+        &["api"],
+        &["--direct", "--delete-branches"],
+    );
+    // the only repo failed
+    assert_eq!(status.code(), Some(exitcode::UNAVAILABLE));
+
+    assert_eq!(branch_tip(&repo, "main"), Some(main));
+    assert_eq!(branch_tip(&repo, "dev"), Some(dev));
+}
+
+#[test]
+fn predicted_conflicts_stop_the_promotion_before_any_write() {
+    let root = TempDir::new().unwrap();
+    let workdir = TempDir::new().unwrap();
+
+    let api = Repository::init_bare(root.path().join("api.git")).unwrap();
+    let base = commit_files(&api, "main", None, &[("README.md", "api\n")]);
+    commit_files(&api, "dev", Some(base), &[("feature.rs", "")]);
+    let web = Repository::init_bare(root.path().join("web.git")).unwrap();
+    let base = commit_files(&web, "main", None, &[("index.html", "web\n")]);
+    let dev = commit_files(&web, "dev", Some(base), &[("index.html", "feature\n")]);
+    let main = commit_files(&web, "main", Some(base), &[("index.html", "hotfix\n")]);
+
+    let cache_dir = workdir.path().join("cache");
+    let status = run_cli(
+        workdir.path(),
+        root.path(),
+        &["api", "web"],
+        &[
+            "--predict-conflicts",
+            "--cache-dir",
+            cache_dir.to_str().unwrap(),
+            "--direct",
+            "--delete-branches",
+        ],
+    );
+    // web is predicted to conflict
+    assert_eq!(status.code(), Some(exitcode::TEMPFAIL));
+
+    assert_eq!(branch_tip(&api, "dev"), None);
+    assert_eq!(branch_tip(&web, "main"), Some(main));
+    assert_eq!(branch_tip(&web, "dev"), Some(dev));
+}
+
+#[test]
+fn missing_destination_branch_is_created_from_source() {
+    let root = TempDir::new().unwrap();
+    let workdir = TempDir::new().unwrap();
+
+    let repo = Repository::init_bare(root.path().join("web.git")).unwrap();
+    let dev = commit_files(&repo, "dev", None, &[("index.html", "")]);
+
+    let status = run_cli(
+        workdir.path(),
+        root.path(),
+        &["web"],
+        &["--create-branches"],
+    );
+    assert!(status.success());
+
+    assert_eq!(branch_tip(&repo, "main"), Some(dev));
+}
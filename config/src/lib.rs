@@ -1,19 +1,266 @@
+mod loader;
+mod pipeline;
+mod select;
+mod token;
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone)]
+pub use loader::{load_config, ConfigError, ConfigLoader, Format};
+pub use pipeline::{Pipeline, Stage};
+pub use select::Selector;
+pub use token::TokenSource;
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    #[default]
+    Github,
+    Gitlab,
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
+pub struct GitlabConfig {
+    /// instance root, e.g. `https://gitlab.com`
+    pub url: String,
+    pub token: String,
+    pub group: String,
+}
+
+/// Where the repos of an owner other than `org_name` are hosted.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
+pub struct OwnerConfig {
+    #[serde(default)]
+    pub provider: Provider,
+    /// instance root, required for gitlab and gitea; API root for github
+    pub url: Option<String>,
+    /// defaults to the top level token
+    pub token: Option<String>,
+    /// where to read `token` from
+    pub token_source: Option<TokenSource>,
+}
+
+/// How pull requests are merged.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+/// What a repo does differently from the command line.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default)]
+pub struct RepoOverrides {
+    /// the repo's branch for logical names, `{"prod": "production"}` for `@prod`
+    #[serde(default)]
+    pub branches: HashMap<String, String>,
+    pub merge_method: Option<MergeMethod>,
+    /// logins asked to review the pull requests opened
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    /// replaces `--create-branches`
+    pub create_branches: Option<bool>,
+}
+
+/// Settings bundled under a name, applied with `--profile`.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default)]
+pub struct Profile {
+    /// replaces `org_name`
+    pub owner: Option<String>,
+    pub is_user: Option<bool>,
+    pub token_source: Option<TokenSource>,
+    /// groups managed unless `--group` picks others
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// A repo is either its name, hosted on github under `org_name`, `owner/name`,
+/// or an object naming its owner or provider, and overriding options:
+/// `{"name": "MyRepo", "owner": "mirror", "provider": "gitlab", "merge_method": "squash"}`.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
+#[serde(untagged)]
+pub enum RepoConfig {
+    Name(String),
+    Repo {
+        name: String,
+        owner: Option<String>,
+        provider: Option<Provider>,
+        #[serde(flatten)]
+        overrides: RepoOverrides,
+    },
+}
+
+/// `owner/name` split in its owner and name, `name` alone has no owner.
+fn split_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once('/') {
+        Some((owner, name)) => (Some(owner), name),
+        None => (None, name),
+    }
+}
+
+impl RepoConfig {
+    fn full_name(&self) -> &str {
+        match self {
+            RepoConfig::Name(name) => name,
+            RepoConfig::Repo { name, .. } => name,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        split_name(self.full_name()).1
+    }
+
+    /// The `owner` of the object, else the one before the name's `/`.
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            RepoConfig::Repo {
+                owner: Some(owner), ..
+            } => Some(owner),
+            _ => split_name(self.full_name()).0,
+        }
+    }
+
+    pub fn provider(&self) -> Option<Provider> {
+        match self {
+            RepoConfig::Name(_) => None,
+            RepoConfig::Repo { provider, .. } => *provider,
+        }
+    }
+
+    pub fn overrides(&self) -> Option<&RepoOverrides> {
+        match self {
+            RepoConfig::Name(_) => None,
+            RepoConfig::Repo { overrides, .. } => Some(overrides),
+        }
+    }
+}
+
+/// The repos to manage and how to reach their hosts, read from JSON, TOML or
+/// YAML.
+#[derive(Deserialize, Serialize, JsonSchema, Clone)]
 pub struct Config {
+    /// the token once loaded; kept in the file for backwards compatibility,
+    /// prefer `token_source`
+    #[serde(default)]
     pub token: String,
+    pub token_source: Option<TokenSource>,
     pub org_name: String,
     pub is_user: bool,
-    pub repos: Vec<String>,
+    pub repos: Vec<RepoConfig>,
+    /// repos added to `repos` when they are listed
+    #[serde(default)]
+    pub select: Vec<Selector>,
+    /// API root of a GitHub Enterprise server, defaults to `https://api.github.com`
+    pub github_api_url: Option<String>,
+    pub gitlab: Option<GitlabConfig>,
+    /// branch of every repo for logical names, unless the repo overrides it:
+    /// `{"dev": "main", "prod": "prod"}`
+    #[serde(default)]
+    pub branches: HashMap<String, String>,
+    #[serde(default)]
+    pub owners: HashMap<String, OwnerConfig>,
+    /// named lists of repos and other groups:
+    /// `{"backend": ["api", "worker"], "all": ["backend", "web"]}`
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+    /// promotion flows, run with `promote <pipeline> --stage <stage>`
+    #[serde(default)]
+    pub pipelines: HashMap<String, Pipeline>,
+    /// whether `repos` was narrowed to groups when loaded
+    #[serde(skip)]
+    pub grouped: bool,
 }
 
-pub fn load_config() -> Result<Config, String> {
-    let text = std::fs::read_to_string("config.json").expect("Err");
-    let ds = &mut serde_json::Deserializer::from_str(&text);
-    let result: Result<Config, _> = serde_path_to_error::deserialize(ds);
-    match result {
-        Ok(config) => Ok(config),
-        Err(e) => Err(format!("Unable to load config: {}", e)),
+impl Config {
+    pub fn owner_of<'a>(&'a self, repo: &'a RepoConfig) -> &'a str {
+        repo.owner().unwrap_or(&self.org_name)
+    }
+
+    /// The repo's own provider, else the one of its owner, else github.
+    pub fn provider_of(&self, repo: &RepoConfig) -> Provider {
+        repo.provider()
+            .or_else(|| {
+                self.owners
+                    .get(self.owner_of(repo))
+                    .map(|owner| owner.provider)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The branch `branch` is on `repo`: itself, unless it is a logical name
+    /// like `@prod`, looked up in the repo's `branches` then the config's.
+    pub fn branch_of(&self, repo: &RepoConfig, branch: &str) -> Result<String, String> {
+        let name = match branch.strip_prefix('@') {
+            Some(name) => name,
+            None => return Ok(branch.to_string()),
+        };
+        repo.overrides()
+            .and_then(|overrides| overrides.branches.get(name))
+            .or_else(|| self.branches.get(name))
+            .cloned()
+            .ok_or_else(|| format!("no branch for `{}`", branch))
     }
+
+    /// Whether `member` names `repo`, by its name or as `owner/name`.
+    fn is_member(&self, repo: &RepoConfig, member: &str) -> bool {
+        repo.name() == member || format!("{}/{}", self.owner_of(repo), repo.name()) == member
+    }
+
+    /// The repos of `groups` and the groups they include, in the config's
+    /// order.
+    pub fn group_repos(&self, groups: &[String]) -> Result<Vec<RepoConfig>, (String, String)> {
+        let mut members = Vec::new();
+        let mut path = Vec::new();
+        for group in groups {
+            self.expand(group, &mut path, &mut members)
+                .map_err(|message| (group.clone(), message))?;
+        }
+        Ok(self
+            .repos
+            .iter()
+            .filter(|repo| members.iter().any(|member| self.is_member(repo, member)))
+            .cloned()
+            .collect())
+    }
+
+    fn expand<'a>(
+        &'a self,
+        group: &'a String,
+        path: &mut Vec<&'a String>,
+        members: &mut Vec<&'a String>,
+    ) -> Result<(), String> {
+        if path.contains(&group) {
+            return Err(format!("it includes itself through `{}`", group));
+        }
+        let group_members = match self.groups.get(group) {
+            Some(group_members) => group_members,
+            None => return Err(format!("no group `{}`", group)),
+        };
+        path.push(group);
+        for member in group_members {
+            if self.groups.contains_key(member) {
+                self.expand(member, path, members)?;
+            } else if self.repos.iter().any(|repo| self.is_member(repo, member)) {
+                members.push(member);
+            } else {
+                return Err(format!("`{}` is neither a repo nor a group", member));
+            }
+        }
+        path.pop();
+        Ok(())
+    }
+}
+
+/// JSON Schema of the config file, for editors to validate it against.
+pub fn schema() -> String {
+    let schema = schemars::schema_for!(Config);
+    serde_json::to_string_pretty(&schema).unwrap() + "\n"
 }
@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use log::warn;
+use serde::Serialize;
+
+use crate::{Config, RepoConfig, TokenSource};
+
+/// Variable naming the config file, after `--config`.
+const CONFIG_VAR: &str = "MULTIGIT_CONFIG";
+/// Names looked for in each config directory, in order.
+const FILE_NAMES: [&str; 4] = ["config.json", "config.toml", "config.yaml", "config.yml"];
+
+/// The syntax of a config file, from its extension; JSON unless it is
+/// `.toml`, `.yaml` or `.yml`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    pub fn of(path: &Path) -> Format {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Format::Toml,
+            Some("yaml" | "yml") => Format::Yaml,
+            _ => Format::Json,
+        }
+    }
+
+    /// Parses `text`, the error naming the path of the faulty key.
+    pub fn parse(self, text: &str) -> Result<Config, String> {
+        match self {
+            Format::Json => {
+                let ds = &mut serde_json::Deserializer::from_str(text);
+                serde_path_to_error::deserialize(ds).map_err(|e| e.to_string())
+            }
+            Format::Toml => {
+                let ds = toml::Deserializer::new(text);
+                serde_path_to_error::deserialize(ds).map_err(|e| e.to_string())
+            }
+            Format::Yaml => {
+                let ds = serde_yaml::Deserializer::from_str(text);
+                serde_path_to_error::deserialize(ds).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// `value` written in this syntax, ending with a newline.
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<String, String> {
+        match self {
+            Format::Json => serde_json::to_string_pretty(value)
+                .map(|text| text + "\n")
+                .map_err(|e| e.to_string()),
+            Format::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            Format::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// no config file at any of these paths
+    NotFound(Vec<PathBuf>),
+    Read {
+        path: PathBuf,
+        message: String,
+    },
+    /// `message` holds the path of the faulty key within the file
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    /// an override variable holds a value its key can't take
+    Env {
+        var: String,
+        message: String,
+    },
+    Token {
+        source: TokenSource,
+        message: String,
+    },
+    /// `--profile` names none of the config's profiles
+    Profile(String),
+    /// no pipeline of this name in the config
+    Pipeline(String),
+    Group {
+        group: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::NotFound(searched) => {
+                let searched: Vec<String> = searched
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                write!(f, "No config file, looked for {}", searched.join(", "))
+            }
+            ConfigError::Read { path, message } => {
+                write!(f, "Unable to read {}: {}", path.display(), message)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "Unable to load config {}: {}", path.display(), message)
+            }
+            ConfigError::Env { var, message } => write!(f, "Invalid {}: {}", var, message),
+            ConfigError::Token { source, message } => {
+                write!(f, "Unable to read the token from {}: {}", source, message)
+            }
+            ConfigError::Profile(profile) => write!(f, "No profile `{}` in the config", profile),
+            ConfigError::Pipeline(pipeline) => {
+                write!(f, "No pipeline `{}` in the config", pipeline)
+            }
+            ConfigError::Group { group, message } => {
+                write!(f, "Invalid group `{}`: {}", group, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Finds the config file and applies the `MULTIGIT_*` overrides on top of it.
+///
+/// The file is the first of: the explicit path (`--config`), the path in
+/// `MULTIGIT_CONFIG`, a config file in the project directory, then one in
+/// `$XDG_CONFIG_HOME/multigit` (`~/.config` without it). In a directory,
+/// `config.json` comes before `config.toml`, `config.yaml` and `config.yml`.
+pub struct ConfigLoader {
+    path: Option<PathBuf>,
+    dir: PathBuf,
+    env: HashMap<String, String>,
+    profile: Option<String>,
+    groups: Vec<String>,
+    pipeline: Option<String>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> ConfigLoader {
+        ConfigLoader::new()
+    }
+}
+
+impl ConfigLoader {
+    /// Loader for the current directory and environment.
+    pub fn new() -> ConfigLoader {
+        ConfigLoader {
+            path: None,
+            dir: PathBuf::from("."),
+            env: std::env::vars().collect(),
+            profile: None,
+            groups: Vec::new(),
+            pipeline: None,
+        }
+    }
+
+    pub fn with_path(mut self, path: Option<PathBuf>) -> ConfigLoader {
+        self.path = path;
+        self
+    }
+
+    /// Directory holding the project-local config.
+    pub fn with_dir(mut self, dir: PathBuf) -> ConfigLoader {
+        self.dir = dir;
+        self
+    }
+
+    /// Variables to read instead of the process environment.
+    pub fn with_env(mut self, env: HashMap<String, String>) -> ConfigLoader {
+        self.env = env;
+        self
+    }
+
+    /// Profile applied over the file's keys, else the one in `MULTIGIT_PROFILE`.
+    pub fn with_profile(mut self, profile: Option<String>) -> ConfigLoader {
+        self.profile = profile;
+        self
+    }
+
+    /// Groups whose repos are the only ones kept, instead of the pipeline's
+    /// or the profile's.
+    pub fn with_groups(mut self, groups: Vec<String>) -> ConfigLoader {
+        self.groups = groups;
+        self
+    }
+
+    /// Pipeline run, whose groups are kept instead of the profile's.
+    pub fn with_pipeline(mut self, pipeline: Option<String>) -> ConfigLoader {
+        self.pipeline = pipeline;
+        self
+    }
+
+    fn var(&self, name: &str) -> Option<&String> {
+        self.env.get(name).filter(|value| !value.is_empty())
+    }
+
+    /// The paths looked at, in order. An explicit path is the only one.
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        if let Some(path) = &self.path {
+            return vec![path.clone()];
+        }
+        if let Some(path) = self.var(CONFIG_VAR) {
+            return vec![PathBuf::from(path)];
+        }
+
+        let mut dirs = vec![self.dir.clone()];
+        let config_home = self
+            .var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| self.var("HOME").map(|home| Path::new(home).join(".config")));
+        if let Some(config_home) = config_home {
+            dirs.push(config_home.join("multigit"));
+        }
+        dirs.iter()
+            .flat_map(|dir| FILE_NAMES.iter().map(move |name| dir.join(name)))
+            .collect()
+    }
+
+    /// The config file to load.
+    pub fn find(&self) -> Result<PathBuf, ConfigError> {
+        let candidates = self.candidates();
+        candidates
+            .iter()
+            .find(|path| path.is_file())
+            .cloned()
+            .ok_or(ConfigError::NotFound(candidates))
+    }
+
+    pub fn load(&self) -> Result<Config, ConfigError> {
+        let path = self.find()?;
+        let text = std::fs::read_to_string(&path).map_err(|e| ConfigError::Read {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        let mut config = Format::of(&path)
+            .parse(&text)
+            .map_err(|message| ConfigError::Parse {
+                path: path.clone(),
+                message,
+            })?;
+        let mut groups = self.apply_profile(&mut config)?;
+        if let Some(name) = &self.pipeline {
+            let pipeline = config
+                .pipelines
+                .get(name)
+                .ok_or_else(|| ConfigError::Pipeline(name.clone()))?;
+            if self.groups.is_empty() && !pipeline.groups.is_empty() {
+                groups = pipeline.groups.clone();
+            }
+        }
+        self.apply_overrides(&mut config)?;
+        self.read_token(&mut config, &path)?;
+        // groups only hold repos named in `repos`
+        if !groups.is_empty() {
+            config.repos = config
+                .group_repos(&groups)
+                .map_err(|(group, message)| ConfigError::Group { group, message })?;
+            config.select.clear();
+            config.grouped = true;
+        }
+        Ok(config)
+    }
+
+    /// Applies the profile and returns the groups to keep.
+    fn apply_profile(&self, config: &mut Config) -> Result<Vec<String>, ConfigError> {
+        let name = match self
+            .profile
+            .as_ref()
+            .or_else(|| self.var("MULTIGIT_PROFILE"))
+        {
+            Some(name) => name,
+            None => return Ok(self.groups.clone()),
+        };
+        let profile = config
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ConfigError::Profile(name.clone()))?;
+        if let Some(owner) = profile.owner {
+            config.org_name = owner;
+        }
+        if let Some(is_user) = profile.is_user {
+            config.is_user = is_user;
+        }
+        if profile.token_source.is_some() {
+            config.token_source = profile.token_source;
+        }
+        if self.groups.is_empty() {
+            Ok(profile.groups)
+        } else {
+            Ok(self.groups.clone())
+        }
+    }
+
+    /// Fills `token` from `token_source`, unless `MULTIGIT_TOKEN` set it, and
+    /// the token of each owner from its own.
+    fn read_token(&self, config: &mut Config, path: &Path) -> Result<(), ConfigError> {
+        for owner in config.owners.values_mut() {
+            if let Some(source) = &owner.token_source {
+                let token = source
+                    .read(&self.env)
+                    .map_err(|message| ConfigError::Token {
+                        source: source.clone(),
+                        message,
+                    })?;
+                owner.token = Some(token);
+            }
+        }
+        if self.var("MULTIGIT_TOKEN").is_some() {
+            return Ok(());
+        }
+        match &config.token_source {
+            Some(source) => {
+                if !config.token.is_empty() {
+                    warn!("{}: `token` is ignored for `token_source`", path.display());
+                }
+                config.token = source
+                    .read(&self.env)
+                    .map_err(|message| ConfigError::Token {
+                        source: source.clone(),
+                        message,
+                    })?;
+            }
+            // local promotions need no token
+            None if config.token.is_empty() => {}
+            None => warn!(
+                "{} holds the token in plain text, prefer `token_source`",
+                path.display()
+            ),
+        }
+        Ok(())
+    }
+
+    /// Overrides single keys from `MULTIGIT_<KEY>` variables: `TOKEN`,
+    /// `ORG_NAME`, `IS_USER`, `GITHUB_API_URL`, `GITLAB_TOKEN` and `REPOS`, a
+    /// comma separated list of names.
+    fn apply_overrides(&self, config: &mut Config) -> Result<(), ConfigError> {
+        if let Some(token) = self.var("MULTIGIT_TOKEN") {
+            config.token = token.clone();
+        }
+        if let Some(org_name) = self.var("MULTIGIT_ORG_NAME") {
+            config.org_name = org_name.clone();
+        }
+        if let Some(is_user) = self.var("MULTIGIT_IS_USER") {
+            config.is_user = match is_user.to_lowercase().as_str() {
+                "true" | "1" | "yes" => true,
+                "false" | "0" | "no" => false,
+                _ => {
+                    return Err(ConfigError::Env {
+                        var: String::from("MULTIGIT_IS_USER"),
+                        message: format!("`{}` is not a boolean", is_user),
+                    })
+                }
+            };
+        }
+        if let Some(api_url) = self.var("MULTIGIT_GITHUB_API_URL") {
+            config.github_api_url = Some(api_url.clone());
+        }
+        if let Some(token) = self.var("MULTIGIT_GITLAB_TOKEN") {
+            match config.gitlab.as_mut() {
+                Some(gitlab) => gitlab.token = token.clone(),
+                None => {
+                    return Err(ConfigError::Env {
+                        var: String::from("MULTIGIT_GITLAB_TOKEN"),
+                        message: String::from("the config has no gitlab section"),
+                    })
+                }
+            }
+        }
+        if let Some(repos) = self.var("MULTIGIT_REPOS") {
+            config.repos = repos
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| RepoConfig::Name(name.to_string()))
+                .collect();
+        }
+        Ok(())
+    }
+}
+
+/// Loads the config from `path`, or the first one found, with the
+/// environment overrides.
+pub fn load_config(path: Option<PathBuf>) -> Result<Config, ConfigError> {
+    ConfigLoader::new().with_path(path).load()
+}
@@ -0,0 +1,56 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::MergeMethod;
+
+/// Branches promoted one into the next, with the rules of each hop:
+/// `{"groups": ["backend"], "stages": [{"name": "dev", "branch": "main"}, {"name": "prod", "branch": "@prod", "require_approval": true}]}`.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default)]
+pub struct Pipeline {
+    /// in promotion order
+    pub stages: Vec<Stage>,
+    /// groups promoted unless `--group` picks others, every repo when empty
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// A stage of a pipeline, the rules applying when promoting into it from the
+/// stage before.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default)]
+pub struct Stage {
+    pub name: String,
+    /// the stage's branch, may be a logical name like `@prod`
+    pub branch: String,
+    /// unless the repo has its own
+    pub merge_method: Option<MergeMethod>,
+    /// merge only approved pull requests without changes requested
+    #[serde(default)]
+    pub require_approval: bool,
+    /// merge only pull requests whose checks all passed
+    #[serde(default)]
+    pub require_checks: bool,
+    /// release tagged on the branch once merged, `{date}` being replaced by
+    /// the day, e.g. `prod-{date}`
+    pub tag: Option<String>,
+    /// delete the previous stage's branch once merged
+    #[serde(default)]
+    pub delete_branch: bool,
+}
+
+impl Pipeline {
+    /// The stage promoted from and the stage `name` promoted into.
+    pub fn hop(&self, name: &str) -> Result<(&Stage, &Stage), String> {
+        let index = self
+            .stages
+            .iter()
+            .position(|stage| stage.name == name)
+            .ok_or_else(|| format!("no stage `{}`", name))?;
+        match index {
+            0 => Err(format!(
+                "`{}` is the first stage, nothing is promoted into it",
+                name
+            )),
+            index => Ok((&self.stages[index - 1], &self.stages[index])),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Repos picked among the ones an owner has, when they are listed:
+/// `{"topics": ["backend"], "language": "rust", "exclude": ["sandbox"]}`.
+/// Every criterion given has to match; archived repos and forks are left out
+/// unless asked for.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selector {
+    /// defaults to `org_name`
+    pub owner: Option<String>,
+    /// glob on the name, `api-*`
+    pub name: Option<String>,
+    /// regular expression the name matches
+    pub regex: Option<String>,
+    /// topics the repo has, all of them
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// slug of a team with access to the repo
+    pub team: Option<String>,
+    /// primary language, case insensitive
+    pub language: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub forks: bool,
+    /// repos selected whether they match or not
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// repos never selected
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Parses the `--select` syntax: comma separated `key=value` terms, `archived`
+/// and `forks` standing alone, e.g. `topic=backend,language=rust,exclude=sandbox`.
+/// `topic`, `include` and `exclude` may be repeated.
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Selector, String> {
+        let mut selector = Selector::default();
+        for term in expression.split(',').map(str::trim) {
+            if term.is_empty() {
+                continue;
+            }
+            let (key, value) = match term.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim().to_string())),
+                None => (term, None),
+            };
+            match (key, value) {
+                ("archived", None) => selector.archived = true,
+                ("forks", None) => selector.forks = true,
+                ("owner", Some(value)) => selector.owner = Some(value),
+                ("name", Some(value)) => selector.name = Some(value),
+                ("regex", Some(value)) => selector.regex = Some(value),
+                ("topic", Some(value)) => selector.topics.push(value),
+                ("team", Some(value)) => selector.team = Some(value),
+                ("language", Some(value)) => selector.language = Some(value),
+                ("include", Some(value)) => selector.include.push(value),
+                ("exclude", Some(value)) => selector.exclude.push(value),
+                _ => return Err(format!("unknown selector term `{}`", term)),
+            }
+        }
+        Ok(selector)
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Where to read the token from instead of the config file:
+/// `{"from": "command", "command": "pass show gh"}`.
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "from", rename_all = "lowercase")]
+pub enum TokenSource {
+    /// an environment variable
+    Env { var: String },
+    /// a shell command printing the token, e.g. `pass show gh`
+    Command { command: String },
+    /// the `hosts.yml` the `gh` cli logs in to
+    Gh {
+        /// defaults to `github.com`
+        host: Option<String>,
+    },
+    /// the OS keyring, the Secret Service on linux
+    Keyring { service: String, user: String },
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenSource::Env { var } => write!(f, "${}", var),
+            TokenSource::Command { command } => write!(f, "`{}`", command),
+            TokenSource::Gh { host } => {
+                write!(f, "gh hosts.yml ({})", host.as_deref().unwrap_or(GH_HOST))
+            }
+            TokenSource::Keyring { service, user } => {
+                write!(f, "keyring ({}, {})", service, user)
+            }
+        }
+    }
+}
+
+const GH_HOST: &str = "github.com";
+
+#[derive(Deserialize)]
+struct GhHost {
+    oauth_token: Option<String>,
+}
+
+impl TokenSource {
+    /// Reads the token, `env` standing for the process environment.
+    pub fn read(&self, env: &HashMap<String, String>) -> Result<String, String> {
+        let token = match self {
+            TokenSource::Env { var } => env
+                .get(var)
+                .cloned()
+                .ok_or_else(|| String::from("the variable is not set"))?,
+            TokenSource::Command { command } => run(command)?,
+            TokenSource::Gh { host } => gh_token(env, host.as_deref().unwrap_or(GH_HOST))?,
+            TokenSource::Keyring { service, user } => keyring::Entry::new(service, user)
+                .and_then(|entry| entry.get_password())
+                .map_err(|e| e.to_string())?,
+        };
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(String::from("the token is empty"));
+        }
+        Ok(token.to_string())
+    }
+}
+
+fn run(command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
+
+/// `$GH_CONFIG_DIR/hosts.yml`, else the one in `$XDG_CONFIG_HOME/gh` or
+/// `~/.config/gh`.
+fn gh_hosts(env: &HashMap<String, String>) -> Option<PathBuf> {
+    let var = |name: &str| env.get(name).filter(|value| !value.is_empty());
+    let dir = var("GH_CONFIG_DIR").map(PathBuf::from).or_else(|| {
+        var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| Path::new(home).join(".config")))
+            .map(|config_home| config_home.join("gh"))
+    })?;
+    Some(dir.join("hosts.yml"))
+}
+
+fn gh_token(env: &HashMap<String, String>, host: &str) -> Result<String, String> {
+    let path = gh_hosts(env).ok_or_else(|| String::from("no gh config directory"))?;
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+    let hosts: HashMap<String, GhHost> =
+        serde_yaml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    hosts
+        .get(host)
+        .ok_or_else(|| format!("not logged in to {}", host))?
+        .oauth_token
+        .clone()
+        // recent gh versions keep the token in the keyring
+        .ok_or_else(|| format!("no token for {} in {}", host, path.display()))
+}
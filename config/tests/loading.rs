@@ -0,0 +1,483 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use config::{ConfigError, ConfigLoader, Format, Selector};
+use tempfile::TempDir;
+
+fn write_config(dir: &Path, org_name: &str) {
+    let config = format!(
+        r#"{{"token": "file-token", "org_name": "{}", "is_user": false, "repos": ["api"]}}"#,
+        org_name
+    );
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("config.json"), config).unwrap();
+}
+
+fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+    vars.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn the_first_file_found_is_loaded() {
+    let root = TempDir::new().unwrap();
+    let project = root.path().join("project");
+    let config_home = root.path().join("xdg");
+    write_config(&config_home.join("multigit"), "from-xdg");
+    std::fs::create_dir_all(&project).unwrap();
+    let xdg = config_home.to_str().unwrap();
+
+    let loader = ConfigLoader::new()
+        .with_dir(project.clone())
+        .with_env(env(&[("XDG_CONFIG_HOME", xdg)]));
+    assert_eq!(loader.load().unwrap().org_name, "from-xdg");
+
+    write_config(&project, "from-project");
+    let loader = ConfigLoader::new()
+        .with_dir(project.clone())
+        .with_env(env(&[("XDG_CONFIG_HOME", xdg)]));
+    assert_eq!(loader.load().unwrap().org_name, "from-project");
+
+    write_config(&root.path().join("env"), "from-env");
+    let env_path = root.path().join("env/config.json");
+    let loader = ConfigLoader::new()
+        .with_dir(project.clone())
+        .with_env(env(&[
+            ("XDG_CONFIG_HOME", xdg),
+            ("MULTIGIT_CONFIG", env_path.to_str().unwrap()),
+        ]));
+    assert_eq!(loader.load().unwrap().org_name, "from-env");
+
+    write_config(&root.path().join("flag"), "from-flag");
+    let loader = ConfigLoader::new()
+        .with_dir(project)
+        .with_env(env(&[("MULTIGIT_CONFIG", env_path.to_str().unwrap())]))
+        .with_path(Some(root.path().join("flag/config.json")));
+    assert_eq!(loader.load().unwrap().org_name, "from-flag");
+}
+
+#[test]
+fn variables_override_single_keys() {
+    let dir = TempDir::new().unwrap();
+    write_config(dir.path(), "rednaks");
+
+    let config = ConfigLoader::new()
+        .with_dir(dir.path().to_path_buf())
+        .with_env(env(&[
+            ("MULTIGIT_TOKEN", "env-token"),
+            ("MULTIGIT_IS_USER", "true"),
+            ("MULTIGIT_REPOS", "web, docs"),
+        ]))
+        .load()
+        .unwrap();
+    assert_eq!(config.token, "env-token");
+    assert_eq!(config.org_name, "rednaks");
+    assert!(config.is_user);
+    let repos: Vec<&str> = config.repos.iter().map(|repo| repo.name()).collect();
+    assert_eq!(repos, ["web", "docs"]);
+
+    let error = ConfigLoader::new()
+        .with_dir(dir.path().to_path_buf())
+        .with_env(env(&[("MULTIGIT_IS_USER", "maybe")]))
+        .load()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ConfigError::Env { .. }));
+}
+
+#[test]
+fn missing_and_invalid_files_are_errors() {
+    let dir = TempDir::new().unwrap();
+    let loader = ConfigLoader::new()
+        .with_dir(dir.path().to_path_buf())
+        .with_env(HashMap::new());
+    match loader.load() {
+        Err(ConfigError::NotFound(searched)) => assert_eq!(
+            searched,
+            ["config.json", "config.toml", "config.yaml", "config.yml"]
+                .map(|name| dir.path().join(name))
+        ),
+        other => panic!("expected NotFound, got {:?}", other.map(|_| ())),
+    }
+
+    std::fs::write(
+        dir.path().join("config.json"),
+        r#"{"token": "t", "org_name": "o", "is_user": "no", "repos": []}"#,
+    )
+    .unwrap();
+    let error = loader.load().err().unwrap();
+    assert!(matches!(error, ConfigError::Parse { .. }));
+    assert!(error.to_string().contains("is_user"));
+}
+
+#[test]
+fn toml_and_yaml_are_read_by_extension() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("config.toml"),
+        r#"
+token = "t"
+org_name = "rednaks"
+is_user = false
+repos = ["api", { name = "web", provider = "gitlab" }]
+"#,
+    )
+    .unwrap();
+    let loader = ConfigLoader::new()
+        .with_dir(dir.path().to_path_buf())
+        .with_env(HashMap::new());
+    let config = loader.load().unwrap();
+    assert_eq!(config.org_name, "rednaks");
+    assert_eq!(config.repos[1].name(), "web");
+
+    let yaml = dir.path().join("multigit.yml");
+    std::fs::write(
+        &yaml,
+        "token: t\norg_name: from-yaml\nis_user: true\nrepos:\n  - api\n",
+    )
+    .unwrap();
+    let config = loader.with_path(Some(yaml)).load().unwrap();
+    assert_eq!(config.org_name, "from-yaml");
+    assert!(config.is_user);
+}
+
+#[test]
+fn parse_errors_name_the_faulty_key() {
+    let toml = "token = \"t\"\norg_name = \"o\"\nis_user = false\nrepos = []\n[gitlab]\nurl = 1\n";
+    let error = Format::Toml.parse(toml).err().unwrap();
+    assert!(error.starts_with("gitlab.url"), "{}", error);
+
+    let yaml =
+        "token: t\norg_name: o\nis_user: false\nrepos: []\nowners:\n  mirror:\n    provider: svn\n";
+    let error = Format::Yaml.parse(yaml).err().unwrap();
+    assert!(error.starts_with("owners.mirror.provider"), "{}", error);
+}
+
+#[test]
+fn the_committed_schema_is_up_to_date() {
+    let committed = include_str!("../config.schema.json");
+    assert!(
+        committed == config::schema(),
+        "config.schema.json is stale, regenerate it with `cargo run -p cli -- config schema`"
+    );
+}
+
+fn load_with_source(
+    dir: &Path,
+    source: &str,
+    vars: &[(&str, &str)],
+) -> Result<String, ConfigError> {
+    let config = format!(
+        r#"{{"token_source": {}, "org_name": "rednaks", "is_user": false, "repos": []}}"#,
+        source
+    );
+    std::fs::write(dir.join("config.json"), config).unwrap();
+    ConfigLoader::new()
+        .with_dir(dir.to_path_buf())
+        .with_env(env(vars))
+        .load()
+        .map(|config| config.token)
+}
+
+#[test]
+fn the_token_is_read_from_its_source() {
+    let dir = TempDir::new().unwrap();
+    let token = load_with_source(
+        dir.path(),
+        r#"{"from": "env", "var": "GH_TOKEN"}"#,
+        &[("GH_TOKEN", "env-token")],
+    );
+    assert_eq!(token.unwrap(), "env-token");
+
+    let token = load_with_source(
+        dir.path(),
+        r#"{"from": "command", "command": "echo '  command-token  '"}"#,
+        &[],
+    );
+    assert_eq!(token.unwrap(), "command-token");
+
+    let gh = dir.path().join("gh");
+    std::fs::create_dir(&gh).unwrap();
+    std::fs::write(
+        gh.join("hosts.yml"),
+        "github.com:\n    user: rednaks\n    oauth_token: gho_xxxx\n    git_protocol: https\n",
+    )
+    .unwrap();
+    let gh_dir = gh.to_str().unwrap();
+    let token = load_with_source(
+        dir.path(),
+        r#"{"from": "gh"}"#,
+        &[("GH_CONFIG_DIR", gh_dir)],
+    );
+    assert_eq!(token.unwrap(), "gho_xxxx");
+    let token = load_with_source(
+        dir.path(),
+        r#"{"from": "gh", "host": "github.example.com"}"#,
+        &[("GH_CONFIG_DIR", gh_dir)],
+    );
+    assert!(matches!(token, Err(ConfigError::Token { .. })));
+}
+
+#[test]
+fn a_failing_source_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let failing = r#"{"from": "command", "command": "exit 3"}"#;
+    let error = load_with_source(dir.path(), failing, &[]).err().unwrap();
+    assert!(error.to_string().contains("`exit 3`"), "{}", error);
+
+    let token = load_with_source(dir.path(), failing, &[("MULTIGIT_TOKEN", "override")]);
+    assert_eq!(token.unwrap(), "override");
+}
+
+fn grouped_loader(dir: &Path) -> ConfigLoader {
+    let config = serde_json::json!({
+        "token": "t",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", "worker", "web", {"name": "api", "owner": "mirror"}],
+        "groups": {
+            "backend": ["api", "worker"],
+            "all": ["web", "backend"],
+            "mirrors": ["mirror/api"],
+            "loop": ["api", "all", "loop"]
+        },
+        "profiles": {
+            "work": {
+                "owner": "acme",
+                "token_source": {"from": "env", "var": "ACME_TOKEN"},
+                "groups": ["mirrors"]
+            }
+        },
+        "pipelines": {
+            "release": {
+                "groups": ["backend"],
+                "stages": [
+                    {"name": "dev", "branch": "main"},
+                    {"name": "staging", "branch": "staging"},
+                    {"name": "prod", "branch": "@prod"}
+                ]
+            }
+        }
+    });
+    std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+    ConfigLoader::new()
+        .with_dir(dir.to_path_buf())
+        .with_env(env(&[("ACME_TOKEN", "acme-token")]))
+}
+
+fn repo_names(config: &config::Config) -> Vec<String> {
+    config
+        .repos
+        .iter()
+        .map(|repo| format!("{}/{}", config.owner_of(repo), repo.name()))
+        .collect()
+}
+
+#[test]
+fn groups_select_repos() {
+    let dir = TempDir::new().unwrap();
+    let groups = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+    let config = grouped_loader(dir.path()).load().unwrap();
+    assert_eq!(repo_names(&config).len(), 4);
+
+    let config = grouped_loader(dir.path())
+        .with_groups(groups(&["backend"]))
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["rednaks/api", "rednaks/worker", "mirror/api"]
+    );
+
+    let config = grouped_loader(dir.path())
+        .with_groups(groups(&["all"]))
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["rednaks/api", "rednaks/worker", "rednaks/web", "mirror/api"]
+    );
+
+    let error = grouped_loader(dir.path())
+        .with_groups(groups(&["loop"]))
+        .load()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ConfigError::Group { .. }), "{}", error);
+    let error = grouped_loader(dir.path())
+        .with_groups(groups(&["frontend"]))
+        .load()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ConfigError::Group { .. }), "{}", error);
+}
+
+#[test]
+fn profiles_bundle_owner_token_and_groups() {
+    let dir = TempDir::new().unwrap();
+
+    let config = grouped_loader(dir.path())
+        .with_profile(Some(String::from("work")))
+        .load()
+        .unwrap();
+    assert_eq!(config.org_name, "acme");
+    assert_eq!(config.token, "acme-token");
+    assert_eq!(repo_names(&config), ["mirror/api"]);
+
+    let config = grouped_loader(dir.path())
+        .with_profile(Some(String::from("work")))
+        .with_groups(vec![String::from("backend")])
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["acme/api", "acme/worker", "mirror/api"]
+    );
+
+    let error = grouped_loader(dir.path())
+        .with_profile(Some(String::from("home")))
+        .load()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ConfigError::Profile(_)));
+}
+
+#[test]
+fn logical_branches_are_looked_up_per_repo() {
+    let config: config::Config = serde_json::from_value(serde_json::json!({
+        "token": "t",
+        "org_name": "rednaks",
+        "is_user": false,
+        "repos": ["api", {"name": "legacy", "branches": {"dev": "master", "prod": "production"}}],
+        "branches": {"dev": "main", "prod": "prod"}
+    }))
+    .unwrap();
+    let (api, legacy) = (&config.repos[0], &config.repos[1]);
+
+    assert_eq!(config.branch_of(api, "@prod").unwrap(), "prod");
+    assert_eq!(config.branch_of(legacy, "@prod").unwrap(), "production");
+    assert_eq!(config.branch_of(legacy, "@dev").unwrap(), "master");
+    assert_eq!(config.branch_of(legacy, "main").unwrap(), "main");
+    assert!(config.branch_of(api, "@staging").is_err());
+}
+
+#[test]
+fn selectors_are_read_from_the_config_and_the_command_line() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("config.toml"),
+        r#"
+token = "t"
+org_name = "rednaks"
+is_user = false
+repos = ["api"]
+
+[[select]]
+topics = ["backend"]
+language = "rust"
+exclude = ["sandbox"]
+"#,
+    )
+    .unwrap();
+    let config = ConfigLoader::new()
+        .with_path(Some(dir.path().join("config.toml")))
+        .with_env(env(&[]))
+        .load()
+        .unwrap();
+    let from_flag: Selector = "topic=backend, language=rust,exclude=sandbox"
+        .parse()
+        .unwrap();
+    assert_eq!(config.select, [from_flag]);
+
+    let selector: Selector = "owner=mirror,name=api-*,forks,archived,team=core,topic=a,topic=b"
+        .parse()
+        .unwrap();
+    assert_eq!(selector.owner.as_deref(), Some("mirror"));
+    assert!(selector.forks && selector.archived);
+    assert_eq!(selector.topics, ["a", "b"]);
+    assert!("colour=blue".parse::<Selector>().is_err());
+    assert!("forks=yes".parse::<Selector>().is_err());
+}
+
+#[test]
+fn repos_of_other_owners_are_named_with_their_owner() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("config.json"),
+        serde_json::json!({
+            "org_name": "rednaks",
+            "is_user": false,
+            "repos": ["api", "acme/web", {"name": "docs", "owner": "mirror"}],
+            "owners": {
+                "acme": {"url": "https://ghe.acme.com/api/v3", "token_source": {"from": "env", "var": "ACME_TOKEN"}}
+            },
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let loader = || ConfigLoader::new().with_dir(dir.path().to_path_buf());
+
+    let config = loader()
+        .with_env(env(&[("ACME_TOKEN", "acme-token")]))
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["rednaks/api", "acme/web", "mirror/docs"]
+    );
+    let acme = &config.owners["acme"];
+    assert_eq!(acme.provider, config::Provider::Github);
+    assert_eq!(acme.token.as_deref(), Some("acme-token"));
+
+    let error = loader().with_env(env(&[])).load().err().unwrap();
+    assert!(matches!(error, ConfigError::Token { .. }), "{}", error);
+}
+
+#[test]
+fn pipelines_select_their_groups_and_hops() {
+    let dir = TempDir::new().unwrap();
+    let groups = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+    let config = grouped_loader(dir.path())
+        .with_pipeline(Some(String::from("release")))
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["rednaks/api", "rednaks/worker", "mirror/api"]
+    );
+    // `--group` wins over the pipeline's groups, which win over the profile's
+    let config = grouped_loader(dir.path())
+        .with_pipeline(Some(String::from("release")))
+        .with_groups(groups(&["mirrors"]))
+        .load()
+        .unwrap();
+    assert_eq!(repo_names(&config), ["mirror/api"]);
+    let config = grouped_loader(dir.path())
+        .with_profile(Some(String::from("work")))
+        .with_pipeline(Some(String::from("release")))
+        .load()
+        .unwrap();
+    assert_eq!(
+        repo_names(&config),
+        ["acme/api", "acme/worker", "mirror/api"]
+    );
+
+    let pipeline = &config.pipelines["release"];
+    let (from, to) = pipeline.hop("prod").unwrap();
+    assert_eq!(
+        (from.branch.as_str(), to.branch.as_str()),
+        ("staging", "@prod")
+    );
+    assert!(pipeline.hop("dev").is_err());
+    assert!(pipeline.hop("qa").is_err());
+
+    let error = grouped_loader(dir.path())
+        .with_pipeline(Some(String::from("nightly")))
+        .load()
+        .err()
+        .unwrap();
+    assert!(matches!(error, ConfigError::Pipeline(_)), "{}", error);
+}
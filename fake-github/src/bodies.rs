@@ -0,0 +1,392 @@
+//! Response bodies shaped like the examples of the GitHub REST API docs.
+
+use serde_json::{json, Value};
+
+use crate::fixture::CheckRunFixture;
+use crate::state::{FakeCommit, FakePull, FakeRelease, FakeRepo};
+
+const DATE: &str = "2024-01-01T00:00:00Z";
+
+pub fn not_found() -> Value {
+    json!({
+        "message": "Not Found",
+        "documentation_url": "https://docs.github.com/rest"
+    })
+}
+
+pub fn message(message: &str) -> Value {
+    json!({
+        "message": message,
+        "documentation_url": "https://docs.github.com/rest"
+    })
+}
+
+pub fn user(api: &str, login: &str, user_type: &str) -> Value {
+    json!({
+        "login": login,
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "gravatar_id": "",
+        "url": format!("{api}/users/{login}"),
+        "html_url": format!("https://github.com/{login}"),
+        "followers_url": format!("{api}/users/{login}/followers"),
+        "following_url": format!("{api}/users/{login}/following{{/other_user}}"),
+        "gists_url": format!("{api}/users/{login}/gists{{/gist_id}}"),
+        "starred_url": format!("{api}/users/{login}/starred{{/owner}}{{/repo}}"),
+        "subscriptions_url": format!("{api}/users/{login}/subscriptions"),
+        "organizations_url": format!("{api}/users/{login}/orgs"),
+        "repos_url": format!("{api}/users/{login}/repos"),
+        "events_url": format!("{api}/users/{login}/events{{/privacy}}"),
+        "received_events_url": format!("{api}/users/{login}/received_events"),
+        "type": user_type,
+        "site_admin": false
+    })
+}
+
+pub fn org(api: &str, login: &str) -> Value {
+    json!({
+        "login": login,
+        "id": 1,
+        "node_id": "MDEyOk9yZ2FuaXphdGlvbjE=",
+        "url": format!("{api}/orgs/{login}"),
+        "repos_url": format!("{api}/orgs/{login}/repos"),
+        "events_url": format!("{api}/orgs/{login}/events"),
+        "hooks_url": format!("{api}/orgs/{login}/hooks"),
+        "issues_url": format!("{api}/orgs/{login}/issues"),
+        "members_url": format!("{api}/orgs/{login}/members{{/member}}"),
+        "public_members_url": format!("{api}/orgs/{login}/public_members{{/member}}"),
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "description": null
+    })
+}
+
+pub fn repo(api: &str, owner: &str, repo: &FakeRepo) -> Value {
+    let full_name = format!("{}/{}", owner, repo.name);
+    let url = format!("{api}/repos/{full_name}");
+    json!({
+        "id": repo.id,
+        "node_id": "MDEwOlJlcG9zaXRvcnkxMjk2MjY5",
+        "name": repo.name,
+        "full_name": full_name,
+        "owner": user(api, owner, "Organization"),
+        "private": repo.private,
+        "visibility": if repo.private { "private" } else { "public" },
+        "html_url": format!("https://github.com/{full_name}"),
+        "description": null,
+        "fork": repo.fork,
+        "url": url,
+        "archive_url": format!("{url}/{{archive_format}}{{/ref}}"),
+        "assignees_url": format!("{url}/assignees{{/user}}"),
+        "blobs_url": format!("{url}/git/blobs{{/sha}}"),
+        "branches_url": format!("{url}/branches{{/branch}}"),
+        "collaborators_url": format!("{url}/collaborators{{/collaborator}}"),
+        "comments_url": format!("{url}/comments{{/number}}"),
+        "commits_url": format!("{url}/commits{{/sha}}"),
+        "compare_url": format!("{url}/compare/{{base}}...{{head}}"),
+        "contents_url": format!("{url}/contents/{{+path}}"),
+        "contributors_url": format!("{url}/contributors"),
+        "deployments_url": format!("{url}/deployments"),
+        "downloads_url": format!("{url}/downloads"),
+        "events_url": format!("{url}/events"),
+        "forks_url": format!("{url}/forks"),
+        "git_commits_url": format!("{url}/git/commits{{/sha}}"),
+        "git_refs_url": format!("{url}/git/refs{{/sha}}"),
+        "git_tags_url": format!("{url}/git/tags{{/sha}}"),
+        "git_url": format!("git:github.com/{full_name}.git"),
+        "issue_comment_url": format!("{url}/issues/comments{{/number}}"),
+        "issue_events_url": format!("{url}/issues/events{{/number}}"),
+        "issues_url": format!("{url}/issues{{/number}}"),
+        "keys_url": format!("{url}/keys{{/key_id}}"),
+        "labels_url": format!("{url}/labels{{/name}}"),
+        "clone_url": format!("https://github.com/{full_name}.git"),
+        "default_branch": repo.default_branch,
+        "archived": repo.archived,
+        "disabled": false,
+        "topics": repo.topics,
+        "language": repo.language,
+        "visibility": "public",
+        "permissions": {"admin": true, "maintain": true, "push": true, "triage": true, "pull": true}
+    })
+}
+
+fn diff_entry(api: &str, full_name: &str, sha: &str, filename: &str) -> Value {
+    json!({
+        "sha": sha,
+        "filename": filename,
+        "status": "modified",
+        "additions": 1,
+        "deletions": 1,
+        "changes": 2,
+        "blob_url": format!("https://github.com/{full_name}/blob/{sha}/{filename}"),
+        "raw_url": format!("https://github.com/{full_name}/raw/{sha}/{filename}"),
+        "contents_url": format!("{api}/repos/{full_name}/contents/{filename}?ref={sha}"),
+        "patch": "@@ -1 +1 @@\n-before\n+after"
+    })
+}
+
+pub fn commit(api: &str, owner: &str, repo: &FakeRepo, commit: &FakeCommit) -> Value {
+    let full_name = format!("{}/{}", owner, repo.name);
+    let sha = &commit.sha;
+    json!({
+        "url": format!("{api}/repos/{full_name}/commits/{sha}"),
+        "sha": sha,
+        "node_id": "MDY6Q29tbWl0NmRjYjA5YjViNTc4NzVmMzM0ZjYxYWViZWQ2OTVlMmU0MTkzZGI1ZQ==",
+        "html_url": format!("https://github.com/{full_name}/commit/{sha}"),
+        "comments_url": format!("{api}/repos/{full_name}/commits/{sha}/comments"),
+        "commit": {
+            "url": format!("{api}/repos/{full_name}/git/commits/{sha}"),
+            "message": commit.message,
+            "author": {"name": commit.author, "email": format!("{}@example.com", commit.author), "date": DATE},
+            "committer": {"name": commit.author, "email": format!("{}@example.com", commit.author), "date": DATE}
+        },
+        "author": user(api, &commit.author, "User"),
+        "committer": user(api, &commit.author, "User"),
+        "parents": [],
+        "files": commit
+            .files
+            .iter()
+            .map(|filename| diff_entry(api, &full_name, sha, filename))
+            .collect::<Vec<Value>>()
+    })
+}
+
+pub fn branch(api: &str, owner: &str, repo: &FakeRepo, name: &str) -> Value {
+    let head = repo.commit(&repo.head(name).unwrap_or_default());
+    json!({
+        "name": name,
+        "commit": commit(api, owner, repo, &head),
+        "protected": repo.protected.contains(name),
+        "protection_url": format!("{api}/repos/{owner}/{}/branches/{name}/protection", repo.name)
+    })
+}
+
+pub fn comparison(
+    api: &str,
+    owner: &str,
+    repo: &FakeRepo,
+    (ahead, behind, merge_base): &(Vec<String>, Vec<String>, Option<String>),
+) -> Value {
+    let status = match (ahead.len(), behind.len()) {
+        (0, 0) => "identical",
+        (_, 0) => "ahead",
+        (0, _) => "behind",
+        _ => "diverged",
+    };
+    let commits: Vec<Value> = ahead
+        .iter()
+        .map(|sha| commit(api, owner, repo, &repo.commit(sha)))
+        .collect();
+    let full_name = format!("{}/{}", owner, repo.name);
+    let files: Vec<Value> = ahead
+        .iter()
+        .flat_map(|sha| {
+            repo.commit(sha)
+                .files
+                .into_iter()
+                .map(move |filename| (sha, filename))
+        })
+        .map(|(sha, filename)| diff_entry(api, &full_name, sha, &filename))
+        .collect();
+    json!({
+        "url": format!("{api}/repos/{full_name}/compare"),
+        "html_url": format!("https://github.com/{full_name}/compare"),
+        "status": status,
+        "ahead_by": ahead.len(),
+        "behind_by": behind.len(),
+        "total_commits": ahead.len(),
+        "commits": commits,
+        "merge_base_commit": merge_base
+            .as_ref()
+            .map(|sha| commit(api, owner, repo, &repo.commit(sha))),
+        "files": files
+    })
+}
+
+pub fn reference(api: &str, owner: &str, repo: &FakeRepo, branch: &str, sha: &str) -> Value {
+    let url = format!("{api}/repos/{owner}/{}", repo.name);
+    json!({
+        "ref": format!("refs/heads/{branch}"),
+        "node_id": "MDM6UmVmcmVmcy9oZWFkcy9mZWF0dXJlQQ==",
+        "url": format!("{url}/git/refs/heads/{branch}"),
+        "object": {
+            "type": "commit",
+            "sha": sha,
+            "url": format!("{url}/git/commits/{sha}")
+        }
+    })
+}
+
+fn pull_pointer(api: &str, owner: &str, repo: &FakeRepo, branch: &str, sha: &str) -> Value {
+    json!({
+        "label": format!("{owner}:{branch}"),
+        "ref": branch,
+        "sha": sha,
+        "user": user(api, owner, "Organization"),
+        "repo": self::repo(api, owner, repo)
+    })
+}
+
+pub fn pull(api: &str, owner: &str, login: &str, repo: &FakeRepo, pull: &FakePull) -> Value {
+    let url = format!("{api}/repos/{owner}/{}/pulls/{}", repo.name, pull.number);
+    let html_url = format!(
+        "https://github.com/{owner}/{}/pull/{}",
+        repo.name, pull.number
+    );
+    let head_sha = repo.head(&pull.head).unwrap_or_default();
+    let base_sha = repo.head(&pull.base).unwrap_or_default();
+    let (mergeable, mergeable_state) = if !pull.open {
+        (Value::Null, "unknown")
+    } else if !repo.conflicts(&pull.base, &pull.head).is_empty() {
+        (json!(false), "dirty")
+    } else {
+        match repo.compare(&pull.base, &pull.head) {
+            Some((_, behind, _)) if !behind.is_empty() => (json!(true), "behind"),
+            _ => (json!(true), "clean"),
+        }
+    };
+    json!({
+        "url": url,
+        "id": 1000 + pull.number,
+        "node_id": "MDExOlB1bGxSZXF1ZXN0MQ==",
+        "html_url": html_url,
+        "diff_url": format!("{html_url}.diff"),
+        "patch_url": format!("{html_url}.patch"),
+        "issue_url": format!("{api}/repos/{owner}/{}/issues/{}", repo.name, pull.number),
+        "commits_url": format!("{url}/commits"),
+        "review_comments_url": format!("{url}/comments"),
+        "review_comment_url": format!("{api}/repos/{owner}/{}/pulls/comments{{/number}}", repo.name),
+        "comments_url": format!("{api}/repos/{owner}/{}/issues/{}/comments", repo.name, pull.number),
+        "statuses_url": format!("{api}/repos/{owner}/{}/statuses/{head_sha}", repo.name),
+        "number": pull.number,
+        "state": if pull.open { "open" } else { "closed" },
+        "locked": false,
+        "title": pull.title,
+        "user": user(api, login, "User"),
+        "body": pull.body,
+        "labels": [],
+        "milestone": null,
+        "active_lock_reason": null,
+        "created_at": DATE,
+        "updated_at": DATE,
+        "closed_at": if pull.open { Value::Null } else { json!(DATE) },
+        "merged_at": if pull.merged { json!(DATE) } else { Value::Null },
+        "merge_commit_sha": pull.merge_commit_sha,
+        "assignee": null,
+        "assignees": [],
+        "requested_reviewers": pull
+            .requested_reviewers
+            .iter()
+            .map(|reviewer| user(api, reviewer, "User"))
+            .collect::<Vec<Value>>(),
+        "requested_teams": [],
+        "head": pull_pointer(api, owner, repo, &pull.head, &head_sha),
+        "base": pull_pointer(api, owner, repo, &pull.base, &base_sha),
+        "_links": {
+            "self": {"href": url},
+            "html": {"href": html_url}
+        },
+        "author_association": "MEMBER",
+        "auto_merge": null,
+        "draft": false,
+        "merged": pull.merged,
+        "mergeable": mergeable,
+        "rebaseable": mergeable,
+        "mergeable_state": mergeable_state,
+        "merged_by": if pull.merged { user(api, login, "User") } else { Value::Null },
+        "comments": 0,
+        "review_comments": 0,
+        "maintainer_can_modify": true,
+        "additions": 0,
+        "deletions": 0,
+        "changed_files": 0
+    })
+}
+
+pub fn check_runs(api: &str, owner: &str, repo: &FakeRepo, sha: &str) -> Value {
+    let runs: Vec<Value> = repo
+        .check_runs
+        .get(sha)
+        .iter()
+        .flat_map(|runs| runs.iter())
+        .enumerate()
+        .map(|(index, run): (usize, &CheckRunFixture)| {
+            json!({
+                "id": index + 1,
+                "name": run.name,
+                "head_sha": sha,
+                "status": run.status,
+                "conclusion": run.conclusion,
+                "html_url": format!("https://github.com/{owner}/{}/runs/{}", repo.name, index + 1),
+                "started_at": DATE,
+                "completed_at": if run.status == "completed" { json!(DATE) } else { Value::Null },
+                "url": format!("{api}/repos/{owner}/{}/check-runs/{}", repo.name, index + 1)
+            })
+        })
+        .collect();
+    json!({
+        "total_count": runs.len(),
+        "check_runs": runs
+    })
+}
+
+pub fn reviews(api: &str, owner: &str, repo: &FakeRepo, pull: &FakePull) -> Value {
+    let head_sha = repo.head(&pull.head);
+    let reviews: Vec<Value> = pull
+        .reviews
+        .iter()
+        .enumerate()
+        .map(|(index, review)| {
+            json!({
+                "id": index + 1,
+                "node_id": "PRR_kwDOABCDEF",
+                "user": user(api, &review.user, "User"),
+                "body": "",
+                "state": review.state,
+                "html_url": format!(
+                    "https://github.com/{owner}/{}/pull/{}#pullrequestreview-{}",
+                    repo.name,
+                    pull.number,
+                    index + 1
+                ),
+                "commit_id": head_sha,
+                "submitted_at": DATE,
+                "author_association": "MEMBER"
+            })
+        })
+        .collect();
+    json!(reviews)
+}
+
+pub fn release(
+    api: &str,
+    owner: &str,
+    login: &str,
+    repo: &FakeRepo,
+    release: &FakeRelease,
+) -> Value {
+    let url = format!("{api}/repos/{owner}/{}/releases/{}", repo.name, release.id);
+    json!({
+        "url": url,
+        "html_url": format!(
+            "https://github.com/{owner}/{}/releases/tag/{}",
+            repo.name, release.tag
+        ),
+        "assets_url": format!("{url}/assets"),
+        "upload_url": format!("{api}/repos/{owner}/{}/releases/{}/assets{{?name,label}}", repo.name, release.id),
+        "tarball_url": format!("{api}/repos/{owner}/{}/tarball/{}", repo.name, release.tag),
+        "zipball_url": format!("{api}/repos/{owner}/{}/zipball/{}", repo.name, release.tag),
+        "id": release.id,
+        "node_id": "MDc6UmVsZWFzZTE=",
+        "tag_name": release.tag,
+        "target_commitish": release.sha,
+        "name": release.name,
+        "body": null,
+        "draft": release.draft,
+        "prerelease": false,
+        "created_at": DATE,
+        "published_at": if release.draft { Value::Null } else { json!(DATE) },
+        "author": user(api, login, "User"),
+        "assets": []
+    })
+}
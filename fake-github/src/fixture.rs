@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct CommitFixture {
+    pub message: Option<String>,
+    pub author: Option<String>,
+    /// paths the commit touches, two sides touching the same path conflict
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReviewFixture {
+    pub user: String,
+    /// `APPROVED`, `CHANGES_REQUESTED`, `COMMENTED`...
+    pub state: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PullFixture {
+    pub head: String,
+    pub base: String,
+    pub title: String,
+    #[serde(default)]
+    pub reviews: Vec<ReviewFixture>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CheckRunFixture {
+    pub name: String,
+    /// `queued`, `in_progress` or `completed`
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct RepoFixture {
+    /// commit metadata by sha, commits missing here get a generated message
+    #[serde(default)]
+    pub commits: HashMap<String, CommitFixture>,
+    /// history of each branch, oldest commit first
+    pub branches: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub protected: Vec<String>,
+    /// pull requests open from the start
+    #[serde(default)]
+    pub pulls: Vec<PullFixture>,
+    /// check runs by commit sha
+    #[serde(default)]
+    pub check_runs: HashMap<String, Vec<CheckRunFixture>>,
+    /// `main` when missing
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub fork: bool,
+    /// listed only to the owner when it is the authenticated user
+    #[serde(default)]
+    pub private: bool,
+    /// primary language
+    pub language: Option<String>,
+}
+
+/// Initial state of a [`crate::FakeGithub`].
+#[derive(Deserialize, Debug, Clone)]
+pub struct Fixture {
+    /// the authenticated user
+    pub login: String,
+    /// organizations the authenticated user belongs to
+    #[serde(default)]
+    pub orgs: Vec<String>,
+    /// owner of every repo
+    pub owner: String,
+    pub repos: BTreeMap<String, RepoFixture>,
+    /// repos of each team of the owner, by slug
+    #[serde(default)]
+    pub teams: BTreeMap<String, Vec<String>>,
+}
+
+impl Fixture {
+    pub fn load(path: &Path) -> Result<Fixture, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read fixture {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Unable to parse fixture {}: {}", path.display(), e))
+    }
+}
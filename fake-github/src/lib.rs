@@ -0,0 +1,128 @@
+//! An in-process, stateful fake of the GitHub REST endpoints the `github`
+//! crate uses, for integration tests.
+//!
+//! ```ignore
+//! let fake = FakeGithub::start(Fixture::load(Path::new("fixtures/promotion.json"))?);
+//! let gh = Github::new(token, owner).with_api_url(fake.url().to_string());
+//! ```
+//!
+//! Branches are linear lists of commits. Merging appends the commits missing
+//! from the base and a merge commit; two sides touching the same file
+//! conflict.
+
+// the pull request bodies are large `json!` literals
+#![recursion_limit = "256"]
+
+mod bodies;
+mod fixture;
+mod routes;
+mod state;
+
+pub use fixture::{CheckRunFixture, CommitFixture, Fixture, PullFixture, RepoFixture};
+pub use state::{FakeCommit, FakePull, FakeRelease};
+
+use std::collections::BTreeMap;
+use std::net::TcpListener;
+use std::sync::{mpsc, Mutex};
+use std::thread::JoinHandle;
+
+use actix_web::dev::ServerHandle;
+use actix_web::{web, App, HttpServer};
+
+use state::State;
+
+pub(crate) struct Shared {
+    /// root url of the fake, links in the responses point to it
+    pub api: String,
+    pub state: Mutex<State>,
+}
+
+pub struct FakeGithub {
+    url: String,
+    shared: web::Data<Shared>,
+    handle: ServerHandle,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeGithub {
+    /// Serves `fixture` on a free local port until dropped.
+    pub fn start(fixture: Fixture) -> FakeGithub {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind the fake github");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let shared = web::Data::new(Shared {
+            api: url.clone(),
+            state: Mutex::new(State::new(fixture)),
+        });
+
+        let data = shared.clone();
+        let (tx, rx) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            actix_web::rt::System::new().block_on(async move {
+                let server = HttpServer::new(move || {
+                    App::new()
+                        .app_data(data.clone())
+                        .configure(routes::configure)
+                })
+                .workers(1)
+                .disable_signals()
+                .listen(listener)
+                .expect("Unable to listen for the fake github")
+                .run();
+                tx.send(server.handle()).unwrap();
+                if let Err(e) = server.await {
+                    log::error!("fake github stopped: {}", e);
+                }
+            });
+        });
+
+        FakeGithub {
+            url,
+            shared,
+            handle: rx.recv().unwrap(),
+            thread: Some(thread),
+        }
+    }
+
+    /// API root to give to `Github::with_api_url`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// History of each branch of `repo`, oldest commit first.
+    pub fn branches(&self, repo: &str) -> BTreeMap<String, Vec<String>> {
+        let state = self.shared.state.lock().unwrap();
+        state
+            .repos
+            .get(repo)
+            .map(|repo| repo.branches.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn pulls(&self, repo: &str) -> Vec<FakePull> {
+        let state = self.shared.state.lock().unwrap();
+        state
+            .repos
+            .get(repo)
+            .map(|repo| repo.pulls.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn releases(&self, repo: &str) -> Vec<FakeRelease> {
+        let state = self.shared.state.lock().unwrap();
+        state
+            .repos
+            .get(repo)
+            .map(|repo| repo.releases.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for FakeGithub {
+    fn drop(&mut self) {
+        // the stop command is sent right away, no need to await it
+        drop(self.handle.stop(false));
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
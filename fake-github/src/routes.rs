@@ -0,0 +1,723 @@
+use std::collections::HashMap;
+
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::bodies;
+use crate::state::{FakePull, FakeRelease, MergeOutcome, State};
+use crate::Shared;
+
+type Data = web::Data<Shared>;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_user)
+        .service(list_my_orgs)
+        .service(get_org)
+        .service(list_org_repos)
+        .service(list_user_repos)
+        .service(list_my_repos)
+        .service(list_team_repos)
+        .service(get_repo)
+        .service(list_branches)
+        .service(get_reference)
+        .service(create_reference)
+        .service(delete_reference)
+        .service(compare)
+        .service(list_commits)
+        .service(list_check_runs)
+        .service(merge_branches)
+        .service(list_pulls)
+        .service(create_pull)
+        .service(get_pull)
+        .service(update_pull)
+        .service(list_reviews)
+        .service(merge_pull)
+        .service(request_reviewers)
+        .service(update_pull_branch)
+        .service(get_release_by_tag)
+        .service(create_release);
+}
+
+fn not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(bodies::not_found())
+}
+
+fn unprocessable(message: &str) -> HttpResponse {
+    HttpResponse::UnprocessableEntity().json(bodies::message(message))
+}
+
+/// The client sends `null` when it has no parameters.
+fn params(body: &web::Bytes) -> HashMap<String, String> {
+    serde_json::from_slice::<Option<HashMap<String, String>>>(body)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Runs `f` on the repo `owner/repo`, answers 404 when there is none.
+fn with_repo(
+    data: &Data,
+    (owner, repo): &(String, String),
+    f: impl FnOnce(&mut State, &str) -> HttpResponse,
+) -> HttpResponse {
+    let mut state = data.state.lock().unwrap();
+    if &state.owner != owner || !state.repos.contains_key(repo) {
+        return not_found();
+    }
+    f(&mut state, repo)
+}
+
+#[get("/user")]
+async fn get_user(data: Data) -> HttpResponse {
+    let state = data.state.lock().unwrap();
+    HttpResponse::Ok().json(bodies::user(&data.api, &state.login, "User"))
+}
+
+#[get("/user/orgs")]
+async fn list_my_orgs(data: Data) -> HttpResponse {
+    let state = data.state.lock().unwrap();
+    let orgs: Vec<_> = state
+        .orgs
+        .iter()
+        .map(|org| bodies::org(&data.api, org))
+        .collect();
+    HttpResponse::Ok().json(orgs)
+}
+
+#[get("/orgs/{org}")]
+async fn get_org(data: Data, path: web::Path<String>) -> HttpResponse {
+    let state = data.state.lock().unwrap();
+    let org = path.into_inner();
+    if state.orgs.contains(&org) {
+        HttpResponse::Ok().json(bodies::org(&data.api, &org))
+    } else {
+        not_found()
+    }
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    per_page: Option<usize>,
+    page: Option<usize>,
+}
+
+/// The page of `repos` asked for, of the owner's repos named.
+fn list_repos<'a>(
+    data: &Data,
+    owner: &str,
+    names: impl Iterator<Item = &'a String>,
+    query: &PageQuery,
+) -> HttpResponse {
+    let state = data.state.lock().unwrap();
+    let per_page = query.per_page.unwrap_or(30);
+    let repos: Vec<_> = names
+        .filter_map(|name| state.repos.get(name))
+        .skip(per_page * (query.page.unwrap_or(1).max(1) - 1))
+        .take(per_page)
+        .map(|repo| bodies::repo(&data.api, owner, repo))
+        .collect();
+    HttpResponse::Ok().json(repos)
+}
+
+/// The owner's repos, private ones only with `private`, 404 when `owner` is
+/// someone else.
+fn list_owner_repos(data: &Data, owner: &str, private: bool, query: &PageQuery) -> HttpResponse {
+    let names: Vec<String> = {
+        let state = data.state.lock().unwrap();
+        if state.owner != owner {
+            return not_found();
+        }
+        state
+            .repos
+            .iter()
+            .filter(|(_, repo)| private || !repo.private)
+            .map(|(name, _)| name.clone())
+            .collect()
+    };
+    list_repos(data, owner, names.iter(), query)
+}
+
+/// 404 when the owner is the authenticated user, a user and not an org.
+#[get("/orgs/{org}/repos")]
+async fn list_org_repos(
+    data: Data,
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+) -> HttpResponse {
+    let is_user = {
+        let state = data.state.lock().unwrap();
+        state.owner == state.login
+    };
+    if is_user {
+        return not_found();
+    }
+    list_owner_repos(&data, &path.into_inner(), true, &query)
+}
+
+/// Public repos only, like github.
+#[get("/users/{user}/repos")]
+async fn list_user_repos(
+    data: Data,
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+) -> HttpResponse {
+    list_owner_repos(&data, &path.into_inner(), false, &query)
+}
+
+/// The repos of the authenticated user, private ones included; none when the
+/// owner is someone else.
+#[get("/user/repos")]
+async fn list_my_repos(data: Data, query: web::Query<PageQuery>) -> HttpResponse {
+    let login = data.state.lock().unwrap().login.clone();
+    match list_owner_repos(&data, &login, true, &query) {
+        response if response.status().is_success() => response,
+        _ => HttpResponse::Ok().json(Vec::<Value>::new()),
+    }
+}
+
+#[get("/orgs/{org}/teams/{team}/repos")]
+async fn list_team_repos(
+    data: Data,
+    path: web::Path<(String, String)>,
+    query: web::Query<PageQuery>,
+) -> HttpResponse {
+    let (org, team) = path.into_inner();
+    let names = {
+        let state = data.state.lock().unwrap();
+        match state.teams.get(&team) {
+            Some(names) if state.owner == org => names.clone(),
+            _ => return not_found(),
+        }
+    };
+    list_repos(&data, &org, names.iter(), &query)
+}
+
+#[get("/repos/{owner}/{repo}")]
+async fn get_repo(data: Data, path: web::Path<(String, String)>) -> HttpResponse {
+    let path = path.into_inner();
+    with_repo(&data, &path, |state, repo| {
+        HttpResponse::Ok().json(bodies::repo(&data.api, &path.0, &state.repos[repo]))
+    })
+}
+
+#[get("/repos/{owner}/{repo}/branches")]
+async fn list_branches(data: Data, path: web::Path<(String, String)>) -> HttpResponse {
+    let path = path.into_inner();
+    with_repo(&data, &path, |state, repo| {
+        let repo = &state.repos[repo];
+        let branches: Vec<_> = repo
+            .branches
+            .keys()
+            .map(|branch| bodies::branch(&data.api, &path.0, repo, branch))
+            .collect();
+        HttpResponse::Ok().json(branches)
+    })
+}
+
+#[get("/repos/{owner}/{repo}/git/refs/heads/{branch:.*}")]
+async fn get_reference(data: Data, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (owner, repo, branch) = path.into_inner();
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        match repo.head(&branch) {
+            Some(sha) => {
+                HttpResponse::Ok().json(bodies::reference(&data.api, &owner, repo, &branch, &sha))
+            }
+            None => not_found(),
+        }
+    })
+}
+
+#[post("/repos/{owner}/{repo}/git/refs")]
+async fn create_reference(
+    data: Data,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let path = path.into_inner();
+    let params = params(&body);
+    with_repo(&data, &path, |state, repo| {
+        let repo = state.repos.get_mut(repo).unwrap();
+        let (reference, sha) = match (params.get("ref"), params.get("sha")) {
+            (Some(reference), Some(sha)) => (reference, sha),
+            _ => return unprocessable("Invalid request"),
+        };
+        let branch = match reference.strip_prefix("refs/heads/") {
+            Some(branch) => branch.to_string(),
+            None => return unprocessable("Reference name must start with refs/heads/"),
+        };
+        if repo.branches.contains_key(&branch) {
+            return unprocessable("Reference already exists");
+        }
+        let history = match repo.history(sha) {
+            Some(history) => history,
+            None => return unprocessable("Object does not exist"),
+        };
+        repo.branches.insert(branch.clone(), history);
+        HttpResponse::Created().json(bodies::reference(&data.api, &path.0, repo, &branch, sha))
+    })
+}
+
+#[delete("/repos/{owner}/{repo}/git/refs/heads/{branch:.*}")]
+async fn delete_reference(data: Data, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (owner, repo, branch) = path.into_inner();
+    with_repo(&data, &(owner, repo), |state, repo| {
+        let repo = state.repos.get_mut(repo).unwrap();
+        if repo.protected.contains(&branch) {
+            return unprocessable("Cannot delete a protected branch");
+        }
+        if repo.default_branch == branch {
+            return unprocessable("Cannot delete the default branch");
+        }
+        match repo.branches.remove(&branch) {
+            Some(_) => HttpResponse::NoContent().finish(),
+            None => unprocessable("Reference does not exist"),
+        }
+    })
+}
+
+#[get("/repos/{owner}/{repo}/compare/{basehead:.*}")]
+async fn compare(data: Data, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (owner, repo, basehead) = path.into_inner();
+    let (base, head) = match basehead.split_once("...") {
+        Some(basehead) => basehead,
+        None => return not_found(),
+    };
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        match repo.compare(base, head) {
+            Some(comparison) => {
+                HttpResponse::Ok().json(bodies::comparison(&data.api, &owner, repo, &comparison))
+            }
+            None => not_found(),
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct CommitsQuery {
+    sha: Option<String>,
+    path: Option<String>,
+    per_page: Option<usize>,
+}
+
+#[get("/repos/{owner}/{repo}/commits")]
+async fn list_commits(
+    data: Data,
+    path: web::Path<(String, String)>,
+    query: web::Query<CommitsQuery>,
+) -> HttpResponse {
+    let path = path.into_inner();
+    with_repo(&data, &path, |state, repo| {
+        let repo = &state.repos[repo];
+        let history = match &query.sha {
+            Some(sha) => repo.history(sha),
+            None => repo.history(&repo.default_branch),
+        };
+        let history = match history {
+            Some(history) => history,
+            None => return not_found(),
+        };
+        let commits: Vec<_> = history
+            .iter()
+            .rev()
+            .map(|sha| repo.commit(sha))
+            .filter(|commit| match &query.path {
+                Some(path) => commit.files.contains(path),
+                None => true,
+            })
+            .take(query.per_page.unwrap_or(30))
+            .map(|commit| bodies::commit(&data.api, &path.0, repo, &commit))
+            .collect();
+        HttpResponse::Ok().json(commits)
+    })
+}
+
+#[get("/repos/{owner}/{repo}/commits/{reference}/check-runs")]
+async fn list_check_runs(data: Data, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (owner, repo, reference) = path.into_inner();
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        let sha = repo.head(&reference).unwrap_or(reference);
+        HttpResponse::Ok().json(bodies::check_runs(&data.api, &owner, repo, &sha))
+    })
+}
+
+#[post("/repos/{owner}/{repo}/merges")]
+async fn merge_branches(
+    data: Data,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let path = path.into_inner();
+    let params = params(&body);
+    with_repo(&data, &path, |state, repo| {
+        let (base, head) = match (params.get("base"), params.get("head")) {
+            (Some(base), Some(head)) => (base, head),
+            _ => return unprocessable("Invalid request"),
+        };
+        let message = params
+            .get("commit_message")
+            .cloned()
+            .unwrap_or_else(|| format!("Merge {} into {}", head, base));
+        let sha = state.next_sha();
+        let repo = state.repos.get_mut(repo).unwrap();
+        if !repo.branches.contains_key(base) || repo.history(head).is_none() {
+            return HttpResponse::NotFound().json(bodies::message("Base or head does not exist"));
+        }
+        match repo.merge(base, head, &message, sha) {
+            MergeOutcome::Merged(sha) => {
+                let commit = repo.commit(&sha);
+                HttpResponse::Created().json(bodies::commit(&data.api, &path.0, repo, &commit))
+            }
+            MergeOutcome::UpToDate => HttpResponse::NoContent().finish(),
+            MergeOutcome::Conflict(files) => HttpResponse::Conflict().json(bodies::message(
+                &format!("Merge conflict in {}", files.join(", ")),
+            )),
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct PullsQuery {
+    state: Option<String>,
+    head: Option<String>,
+    base: Option<String>,
+}
+
+#[get("/repos/{owner}/{repo}/pulls")]
+async fn list_pulls(
+    data: Data,
+    path: web::Path<(String, String)>,
+    query: web::Query<PullsQuery>,
+) -> HttpResponse {
+    let path = path.into_inner();
+    with_repo(&data, &path, |state, repo| {
+        let repo = &state.repos[repo];
+        let owner = &path.0;
+        let pulls: Vec<_> = repo
+            .pulls
+            .iter()
+            .filter(|pull| match query.state.as_deref() {
+                Some("closed") => !pull.open,
+                Some("all") => true,
+                _ => pull.open,
+            })
+            // github filters `head` as `owner:branch` and ignores it otherwise
+            .filter(|pull| match &query.head {
+                Some(head) if head.contains(':') => head == &format!("{}:{}", owner, pull.head),
+                _ => true,
+            })
+            .filter(|pull| match &query.base {
+                Some(base) => base == &pull.base,
+                None => true,
+            })
+            .map(|pull| bodies::pull(&data.api, owner, &state.login, repo, pull))
+            .collect();
+        HttpResponse::Ok().json(pulls)
+    })
+}
+
+#[post("/repos/{owner}/{repo}/pulls")]
+async fn create_pull(
+    data: Data,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let path = path.into_inner();
+    let params = params(&body);
+    with_repo(&data, &path, |state, repo| {
+        let login = state.login.clone();
+        let repo = state.repos.get_mut(repo).unwrap();
+        let (head, base, title) =
+            match (params.get("head"), params.get("base"), params.get("title")) {
+                (Some(head), Some(base), Some(title)) => (head, base, title),
+                _ => return unprocessable("Validation Failed"),
+            };
+        if !repo.branches.contains_key(head) || !repo.branches.contains_key(base) {
+            return unprocessable("Validation Failed");
+        }
+        if repo
+            .pulls
+            .iter()
+            .any(|pull| pull.open && &pull.head == head && &pull.base == base)
+        {
+            return unprocessable("A pull request already exists");
+        }
+        match repo.compare(base, head) {
+            Some((ahead, _, _)) if !ahead.is_empty() => {}
+            _ => return unprocessable("No commits between base and head"),
+        }
+
+        let pull = FakePull {
+            number: repo.pulls.len() as u64 + 1,
+            title: title.clone(),
+            body: params.get("body").cloned(),
+            head: head.clone(),
+            base: base.clone(),
+            open: true,
+            merged: false,
+            merge_commit_sha: None,
+            merge_method: None,
+            requested_reviewers: vec![],
+            reviews: vec![],
+        };
+        let mut response = bodies::pull(&data.api, &path.0, &login, repo, &pull);
+        // github computes the mergeability in the background, after answering
+        response["mergeable"] = Value::Null;
+        response["rebaseable"] = Value::Null;
+        response["mergeable_state"] = json!("unknown");
+        repo.pulls.push(pull);
+        HttpResponse::Created().json(response)
+    })
+}
+
+#[get("/repos/{owner}/{repo}/pulls/{number}")]
+async fn get_pull(data: Data, path: web::Path<(String, String, u64)>) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        match repo.pulls.iter().find(|pull| pull.number == number) {
+            Some(pull) => {
+                HttpResponse::Ok().json(bodies::pull(&data.api, &owner, &state.login, repo, pull))
+            }
+            None => not_found(),
+        }
+    })
+}
+
+/// Only closing is supported, the other fields are ignored.
+#[patch("/repos/{owner}/{repo}/pulls/{number}")]
+async fn update_pull(
+    data: Data,
+    path: web::Path<(String, String, u64)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    let params = params(&body);
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let login = state.login.clone();
+        let repo = state.repos.get_mut(repo).unwrap();
+        let pull = match repo.pulls.iter_mut().find(|pull| pull.number == number) {
+            Some(pull) => pull,
+            None => return not_found(),
+        };
+        if params.get("state").map(String::as_str) == Some("closed") && !pull.merged {
+            pull.open = false;
+        }
+        let pull = pull.clone();
+        HttpResponse::Ok().json(bodies::pull(&data.api, &owner, &login, repo, &pull))
+    })
+}
+
+#[get("/repos/{owner}/{repo}/pulls/{number}/reviews")]
+async fn list_reviews(data: Data, path: web::Path<(String, String, u64)>) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        match repo.pulls.iter().find(|pull| pull.number == number) {
+            Some(pull) => HttpResponse::Ok().json(bodies::reviews(&data.api, &owner, repo, pull)),
+            None => not_found(),
+        }
+    })
+}
+
+#[put("/repos/{owner}/{repo}/pulls/{number}/merge")]
+async fn merge_pull(
+    data: Data,
+    path: web::Path<(String, String, u64)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    let params = params(&body);
+    let merge_method = params
+        .get("merge_method")
+        .cloned()
+        .unwrap_or_else(|| String::from("merge"));
+    if !["merge", "squash", "rebase"].contains(&merge_method.as_str()) {
+        return unprocessable("Invalid merge_method");
+    }
+    with_repo(&data, &(owner, repo), |state, repo| {
+        let sha = state.next_sha();
+        let repo = state.repos.get_mut(repo).unwrap();
+        let pull = match repo.pulls.iter().find(|pull| pull.number == number) {
+            Some(pull) => pull.clone(),
+            None => return not_found(),
+        };
+        if !pull.open {
+            return HttpResponse::MethodNotAllowed()
+                .json(bodies::message("Pull Request is not mergeable"));
+        }
+        let message = format!("Merge pull request #{} from {}", pull.number, pull.head);
+        match repo.merge(&pull.base, &pull.head, &message, sha) {
+            MergeOutcome::Merged(sha) => {
+                let pull = repo
+                    .pulls
+                    .iter_mut()
+                    .find(|pull| pull.number == number)
+                    .unwrap();
+                pull.open = false;
+                pull.merged = true;
+                pull.merge_commit_sha = Some(sha.clone());
+                pull.merge_method = Some(merge_method);
+                HttpResponse::Ok().json(json!({
+                    "sha": sha,
+                    "merged": true,
+                    "message": "Pull Request successfully merged"
+                }))
+            }
+            MergeOutcome::UpToDate | MergeOutcome::Conflict(_) => HttpResponse::MethodNotAllowed()
+                .json(bodies::message("Pull Request is not mergeable")),
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct ReviewRequest {
+    reviewers: Vec<String>,
+}
+
+#[post("/repos/{owner}/{repo}/pulls/{number}/requested_reviewers")]
+async fn request_reviewers(
+    data: Data,
+    path: web::Path<(String, String, u64)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    let reviewers = match serde_json::from_slice::<ReviewRequest>(&body) {
+        Ok(request) => request.reviewers,
+        Err(_) => return unprocessable("Validation Failed"),
+    };
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let login = state.login.clone();
+        let repo = state.repos.get_mut(repo).unwrap();
+        let pull = match repo.pulls.iter_mut().find(|pull| pull.number == number) {
+            Some(pull) => pull,
+            None => return not_found(),
+        };
+        // github refuses to ask the author for a review
+        if reviewers.contains(&login) {
+            return unprocessable("Review cannot be requested from pull request author.");
+        }
+        for reviewer in reviewers {
+            if !pull.requested_reviewers.contains(&reviewer) {
+                pull.requested_reviewers.push(reviewer);
+            }
+        }
+        let pull = pull.clone();
+        HttpResponse::Created().json(bodies::pull(&data.api, &owner, &login, repo, &pull))
+    })
+}
+
+#[put("/repos/{owner}/{repo}/pulls/{number}/update-branch")]
+async fn update_pull_branch(
+    data: Data,
+    path: web::Path<(String, String, u64)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let (owner, repo, number) = path.into_inner();
+    let params = params(&body);
+    with_repo(&data, &(owner, repo), |state, repo| {
+        let sha = state.next_sha();
+        let repo = state.repos.get_mut(repo).unwrap();
+        let pull = match repo.pulls.iter().find(|pull| pull.number == number) {
+            Some(pull) => pull.clone(),
+            None => return not_found(),
+        };
+        if let Some(expected) = params.get("expected_head_sha") {
+            if repo.head(&pull.head).as_ref() != Some(expected) {
+                return unprocessable("expected head sha didn't match current head ref");
+            }
+        }
+        let message = format!("Merge branch '{}' into {}", pull.base, pull.head);
+        match repo.merge(&pull.head, &pull.base, &message, sha) {
+            MergeOutcome::Merged(_) | MergeOutcome::UpToDate => {
+                HttpResponse::Accepted().json(json!({
+                    "message": "Updating pull request branch.",
+                    "url": format!("https://github.com/{}/pull/{}", repo.name, number)
+                }))
+            }
+            MergeOutcome::Conflict(_) => unprocessable("merge conflict between base and head"),
+        }
+    })
+}
+
+#[get("/repos/{owner}/{repo}/releases/tags/{tag}")]
+async fn get_release_by_tag(data: Data, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (owner, repo, tag) = path.into_inner();
+    with_repo(&data, &(owner.clone(), repo), |state, repo| {
+        let repo = &state.repos[repo];
+        // drafts aren't found by tag
+        match repo
+            .releases
+            .iter()
+            .find(|release| release.tag == tag && !release.draft)
+        {
+            Some(release) => HttpResponse::Ok().json(bodies::release(
+                &data.api,
+                &owner,
+                &state.login,
+                repo,
+                release,
+            )),
+            None => not_found(),
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct NewRelease {
+    tag_name: String,
+    target_commitish: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[post("/repos/{owner}/{repo}/releases")]
+async fn create_release(
+    data: Data,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+) -> HttpResponse {
+    let path = path.into_inner();
+    let new_release = match serde_json::from_slice::<NewRelease>(&body) {
+        Ok(new_release) => new_release,
+        Err(_) => return unprocessable("Validation Failed"),
+    };
+    with_repo(&data, &path, |state, repo| {
+        let login = state.login.clone();
+        let repo = state.repos.get_mut(repo).unwrap();
+        if repo
+            .releases
+            .iter()
+            .any(|release| release.tag == new_release.tag_name)
+        {
+            return unprocessable("Validation Failed");
+        }
+        let target = new_release
+            .target_commitish
+            .unwrap_or_else(|| repo.default_branch.clone());
+        let sha = match repo
+            .history(&target)
+            .and_then(|history| history.last().cloned())
+        {
+            Some(sha) => sha,
+            None => return unprocessable("Validation Failed"),
+        };
+
+        let release = FakeRelease {
+            id: repo.releases.len() as u64 + 1,
+            name: new_release
+                .name
+                .unwrap_or_else(|| new_release.tag_name.clone()),
+            tag: new_release.tag_name,
+            sha,
+            draft: new_release.draft,
+        };
+        let response = bodies::release(&data.api, &path.0, &login, repo, &release);
+        repo.releases.push(release);
+        HttpResponse::Created().json(response)
+    })
+}
@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::fixture::{CheckRunFixture, Fixture, ReviewFixture};
+
+#[derive(Debug, Clone)]
+pub struct FakeCommit {
+    pub sha: String,
+    pub message: String,
+    pub author: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakePull {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub head: String,
+    pub base: String,
+    pub open: bool,
+    pub merged: bool,
+    pub merge_commit_sha: Option<String>,
+    /// `merge_method` of the merge request; the fake always makes a merge commit
+    pub merge_method: Option<String>,
+    pub requested_reviewers: Vec<String>,
+    pub reviews: Vec<ReviewFixture>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeRelease {
+    pub id: u64,
+    pub tag: String,
+    /// commit the tag points to
+    pub sha: String,
+    pub name: String,
+    pub draft: bool,
+}
+
+#[derive(Debug)]
+pub enum MergeOutcome {
+    /// holds the merge commit sha
+    Merged(String),
+    UpToDate,
+    /// holds the files touched on both sides
+    Conflict(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct FakeRepo {
+    pub id: u64,
+    pub name: String,
+    pub commits: HashMap<String, FakeCommit>,
+    /// history of each branch, oldest commit first
+    pub branches: BTreeMap<String, Vec<String>>,
+    pub protected: HashSet<String>,
+    pub pulls: Vec<FakePull>,
+    pub releases: Vec<FakeRelease>,
+    pub check_runs: HashMap<String, Vec<CheckRunFixture>>,
+    pub default_branch: String,
+    pub archived: bool,
+    pub topics: Vec<String>,
+    pub fork: bool,
+    pub private: bool,
+    pub language: Option<String>,
+}
+
+impl FakeRepo {
+    pub fn commit(&self, sha: &str) -> FakeCommit {
+        self.commits
+            .get(sha)
+            .cloned()
+            .unwrap_or_else(|| FakeCommit {
+                sha: sha.to_string(),
+                message: format!("Commit {}", sha),
+                author: String::from("octocat"),
+                files: vec![],
+            })
+    }
+
+    /// History of a branch, or of a commit reachable from one of the branches.
+    pub fn history(&self, reference: &str) -> Option<Vec<String>> {
+        if let Some(history) = self.branches.get(reference) {
+            return Some(history.clone());
+        }
+        self.branches.values().find_map(|history| {
+            history
+                .iter()
+                .position(|sha| sha == reference)
+                .map(|position| history[..=position].to_vec())
+        })
+    }
+
+    pub fn head(&self, branch: &str) -> Option<String> {
+        self.branches
+            .get(branch)
+            .and_then(|history| history.last().cloned())
+    }
+
+    /// Commits on `head` missing from `base`, commits on `base` missing from
+    /// `head` and their last common commit.
+    pub fn compare(
+        &self,
+        base: &str,
+        head: &str,
+    ) -> Option<(Vec<String>, Vec<String>, Option<String>)> {
+        let base_history = self.history(base)?;
+        let head_history = self.history(head)?;
+        let base_set: HashSet<&String> = base_history.iter().collect();
+        let head_set: HashSet<&String> = head_history.iter().collect();
+
+        let ahead = head_history
+            .iter()
+            .filter(|sha| !base_set.contains(sha))
+            .cloned()
+            .collect();
+        let behind = base_history
+            .iter()
+            .filter(|sha| !head_set.contains(sha))
+            .cloned()
+            .collect();
+        let merge_base = head_history
+            .iter()
+            .rev()
+            .find(|sha| base_set.contains(sha))
+            .cloned();
+        Some((ahead, behind, merge_base))
+    }
+
+    /// Files touched on both sides since the merge base.
+    pub fn conflicts(&self, base: &str, head: &str) -> Vec<String> {
+        let (ahead, behind, _) = match self.compare(base, head) {
+            Some(comparison) => comparison,
+            None => return vec![],
+        };
+        let files = |shas: &Vec<String>| -> HashSet<String> {
+            shas.iter().flat_map(|sha| self.commit(sha).files).collect()
+        };
+        let mut both_sides: Vec<String> = files(&ahead)
+            .intersection(&files(&behind))
+            .cloned()
+            .collect();
+        both_sides.sort();
+        both_sides
+    }
+
+    /// Merges `head` into `base` with a merge commit named `sha`.
+    pub fn merge(&mut self, base: &str, head: &str, message: &str, sha: String) -> MergeOutcome {
+        let (ahead, _, _) = match self.compare(base, head) {
+            Some(comparison) => comparison,
+            None => return MergeOutcome::UpToDate,
+        };
+        if ahead.is_empty() {
+            return MergeOutcome::UpToDate;
+        }
+        let conflicts = self.conflicts(base, head);
+        if !conflicts.is_empty() {
+            return MergeOutcome::Conflict(conflicts);
+        }
+
+        self.commits.insert(
+            sha.clone(),
+            FakeCommit {
+                sha: sha.clone(),
+                message: message.to_string(),
+                author: String::from("web-flow"),
+                files: vec![],
+            },
+        );
+        let history = self.branches.get_mut(base).unwrap();
+        history.extend(ahead);
+        history.push(sha.clone());
+        MergeOutcome::Merged(sha)
+    }
+}
+
+#[derive(Debug)]
+pub struct State {
+    pub login: String,
+    pub orgs: Vec<String>,
+    pub owner: String,
+    pub repos: BTreeMap<String, FakeRepo>,
+    /// repos of each team of the owner, by slug
+    pub teams: BTreeMap<String, Vec<String>>,
+    last_sha: u64,
+}
+
+impl State {
+    pub fn new(fixture: Fixture) -> State {
+        let repos = fixture
+            .repos
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, repo))| {
+                let commits = repo
+                    .commits
+                    .into_iter()
+                    .map(|(sha, commit)| {
+                        let fake_commit = FakeCommit {
+                            sha: sha.clone(),
+                            message: commit.message.unwrap_or_else(|| format!("Commit {}", sha)),
+                            author: commit.author.unwrap_or_else(|| String::from("octocat")),
+                            files: commit.files,
+                        };
+                        (sha, fake_commit)
+                    })
+                    .collect();
+                let pulls = repo
+                    .pulls
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, pull)| FakePull {
+                        number: index as u64 + 1,
+                        title: pull.title,
+                        body: None,
+                        head: pull.head,
+                        base: pull.base,
+                        open: true,
+                        merged: false,
+                        merge_commit_sha: None,
+                        merge_method: None,
+                        requested_reviewers: vec![],
+                        reviews: pull.reviews,
+                    })
+                    .collect();
+                let fake_repo = FakeRepo {
+                    id: index as u64 + 1,
+                    name: name.clone(),
+                    commits,
+                    branches: repo.branches,
+                    protected: repo.protected.into_iter().collect(),
+                    pulls,
+                    releases: vec![],
+                    check_runs: repo.check_runs,
+                    default_branch: repo.default_branch.unwrap_or_else(|| String::from("main")),
+                    archived: repo.archived,
+                    topics: repo.topics,
+                    fork: repo.fork,
+                    private: repo.private,
+                    language: repo.language,
+                };
+                (name, fake_repo)
+            })
+            .collect();
+
+        State {
+            login: fixture.login,
+            orgs: fixture.orgs,
+            owner: fixture.owner,
+            repos,
+            teams: fixture.teams,
+            last_sha: 0,
+        }
+    }
+
+    /// A fresh 40 hex digits sha.
+    pub fn next_sha(&mut self) -> String {
+        self.last_sha += 1;
+        format!("{:040x}", self.last_sha)
+    }
+}
@@ -0,0 +1,187 @@
+mod model;
+
+pub use model::*;
+
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct ForgeError {
+    pub message: String,
+    pub extra_info: Option<String>,
+}
+
+impl ForgeError {
+    pub fn new(message: String) -> ForgeError {
+        ForgeError {
+            message,
+            extra_info: None,
+        }
+    }
+
+    pub fn unsupported(operation: &str) -> ForgeError {
+        ForgeError::new(format!("{} is not supported by this forge", operation))
+    }
+
+    pub fn error_message(&self) -> String {
+        self.message.clone()
+    }
+
+    pub fn extra_info(&self) -> Option<String> {
+        self.extra_info.clone()
+    }
+}
+
+/// Operations the promotion flow needs from a git hosting, implemented by
+/// `github::GithubForge`, `gitlab::Gitlab`, `gitea::Gitea` and
+/// `local_git::LocalGit`.
+///
+/// Pull request operations are optional: forges without pull requests keep
+/// the default implementations and can only be driven with direct merges.
+#[async_trait]
+pub trait Forge: Send + Sync {
+    async fn get_repo(&self, name: &str) -> Result<Repository, ForgeError>;
+
+    /// Every repo of the forge's owner.
+    async fn list_repos(&self) -> Result<Vec<ListedRepo>, ForgeError> {
+        Err(ForgeError::unsupported("listing repos"))
+    }
+
+    /// Names of the repos the team `team` of the owner has access to.
+    async fn list_team_repos(&self, _team: &str) -> Result<Vec<String>, ForgeError> {
+        Err(ForgeError::unsupported("teams"))
+    }
+
+    async fn list_branches(&self, repo: &Repository) -> Result<Vec<Branch>, ForgeError>;
+
+    /// Compares `base` against `head`: `status` is the one of `base` relative
+    /// to `head`, `commits` and `files` are the ones on `base` since the merge
+    /// base.
+    async fn compare_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+    ) -> Result<Comparison, ForgeError>;
+
+    async fn create_branch(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        from: &str,
+    ) -> Result<(), ForgeError>;
+
+    async fn delete_branch(&self, repo: &Repository, branch: &str) -> Result<(), ForgeError>;
+
+    /// Merges `head` into `base` without a pull request.
+    async fn merge_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+        commit_message: &str,
+    ) -> Result<BranchMerge, ForgeError>;
+
+    /// Finds the open pull request of `from` into `to`.
+    async fn find_pull(
+        &self,
+        _repo: &Repository,
+        _from: &str,
+        _to: &str,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        Err(ForgeError::unsupported("pull requests"))
+    }
+
+    async fn get_pull(&self, _repo: &Repository, _number: u64) -> Result<PullRequest, ForgeError> {
+        Err(ForgeError::unsupported("pull requests"))
+    }
+
+    async fn create_pull(
+        &self,
+        _repo: &Repository,
+        _from: &str,
+        _to: &str,
+        _title: &str,
+        _body: Option<&str>,
+    ) -> Result<PullRequest, ForgeError> {
+        Err(ForgeError::unsupported("pull requests"))
+    }
+
+    async fn merge_pull(
+        &self,
+        _repo: &Repository,
+        _pull_request: &PullRequest,
+        _method: MergeMethod,
+    ) -> Result<PullMerge, ForgeError> {
+        Err(ForgeError::unsupported("pull requests"))
+    }
+
+    async fn close_pull(
+        &self,
+        _repo: &Repository,
+        _pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        Err(ForgeError::unsupported("pull requests"))
+    }
+
+    /// Brings the pull request head branch up to date with its base.
+    async fn update_pull(
+        &self,
+        _repo: &Repository,
+        _pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        Err(ForgeError::unsupported("updating pull requests"))
+    }
+
+    async fn list_checks(&self, _repo: &Repository, _sha: &str) -> Result<Vec<Check>, ForgeError> {
+        Ok(vec![])
+    }
+
+    /// Asks `reviewers`, user logins, to review the pull request.
+    async fn request_reviewers(
+        &self,
+        _repo: &Repository,
+        _pull_request: &PullRequest,
+        _reviewers: &[String],
+    ) -> Result<(), ForgeError> {
+        Err(ForgeError::unsupported("requesting reviewers"))
+    }
+
+    /// Reviews of the pull request, oldest first.
+    async fn list_reviews(
+        &self,
+        _repo: &Repository,
+        _number: u64,
+    ) -> Result<Vec<Review>, ForgeError> {
+        Ok(vec![])
+    }
+
+    async fn find_release(
+        &self,
+        _repo: &Repository,
+        _tag: &str,
+    ) -> Result<Option<Release>, ForgeError> {
+        Err(ForgeError::unsupported("releases"))
+    }
+
+    /// Publishes a release, tagging `target` as `tag` if the tag doesn't exist.
+    async fn create_release(
+        &self,
+        _repo: &Repository,
+        _tag: &str,
+        _target: &str,
+        _name: &str,
+        _draft: bool,
+    ) -> Result<Release, ForgeError> {
+        Err(ForgeError::unsupported("releases"))
+    }
+
+    /// Last author of `path` on `branch`, when the forge knows it.
+    async fn last_author(
+        &self,
+        _repo: &Repository,
+        _branch: &str,
+        _path: &str,
+    ) -> Result<Option<String>, ForgeError> {
+        Ok(None)
+    }
+}
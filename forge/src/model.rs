@@ -0,0 +1,125 @@
+#[derive(Debug, Clone)]
+pub struct Repository {
+    pub name: String,
+    pub full_name: String,
+    pub web_url: String,
+    pub clone_url: String,
+    pub default_branch: Option<String>,
+    /// read-only: archived, or disabled on github
+    pub archived: bool,
+}
+
+/// A repo as listed, with what repo selectors match on.
+#[derive(Debug, Clone)]
+pub struct ListedRepo {
+    pub name: String,
+    pub archived: bool,
+    pub fork: bool,
+    pub topics: Vec<String>,
+    /// primary language
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub name: String,
+    pub sha: String,
+    pub protected: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompareStatus {
+    Ahead,
+    Behind,
+    Diverged,
+    Identical,
+}
+
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub status: CompareStatus,
+    pub ahead_by: u64,
+    pub behind_by: u64,
+    pub commits: Vec<Commit>,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum BranchMerge {
+    /// holds the sha of the commit `base` now points to
+    Merged(String),
+    NothingToMerge,
+    Conflict,
+}
+
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub head: String,
+    pub base: String,
+    pub head_sha: String,
+    pub mergeable: Option<bool>,
+    /// in github's vocabulary (`clean`, `behind`, `dirty`...), other forges map
+    /// their own states onto it
+    pub mergeable_state: Option<String>,
+}
+
+/// How a pull request lands on its base.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Debug, Clone)]
+pub struct PullMerge {
+    pub merged: bool,
+    pub sha: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckState {
+    Pending,
+    Success,
+    Failure,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+#[derive(Debug, Clone)]
+pub struct Review {
+    pub author: Option<String>,
+    pub state: ReviewState,
+}
+
+#[derive(Debug, Clone)]
+pub struct Release {
+    pub tag: String,
+    pub url: String,
+    pub draft: bool,
+}
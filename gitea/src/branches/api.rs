@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use super::response::Branch;
+use crate::{encode, parse, Gitea, GiteaAPIError, GiteaAPIResponseError, PageError};
+
+impl Gitea {
+    pub async fn list_branches(&self, repo: &str) -> Result<Vec<Branch>, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/branches", self.repo_path(repo));
+
+        match self.get_pages(endpoint, &[], "branches").await {
+            Ok(branches) => Ok(branches),
+            Err(PageError::Parse(e)) => Err(e),
+            Err(PageError::Status(status_code)) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn create_branch(
+        &self,
+        repo: &str,
+        branch: &str,
+        from: &str,
+    ) -> Result<Branch, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/branches", self.repo_path(repo));
+        let mut params = HashMap::with_capacity(2);
+        params.insert("new_branch_name", branch);
+        params.insert("old_branch_name", from);
+
+        match self.post(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "created branch"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Branch already exists"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo or source branch not found"),
+                })),
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Branch creation is not allowed, is the repo a mirror?"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn delete_branch(
+        &self,
+        repo: &str,
+        branch: &str,
+    ) -> Result<(), Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/branches/{}", self.repo_path(repo), encode(branch));
+
+        match self.delete(endpoint).await {
+            Ok(_) => Ok(()),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Branch not found"),
+                })),
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Protected or default branches can't be deleted"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
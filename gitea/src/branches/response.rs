@@ -0,0 +1,14 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct BranchCommit {
+    pub id: String,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Branch {
+    pub name: String,
+    pub commit: BranchCommit,
+    pub protected: bool,
+}
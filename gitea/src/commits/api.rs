@@ -0,0 +1,85 @@
+use super::response::{CombinedStatus, Commit, Compare};
+use crate::{encode, parse, Gitea, GiteaAPIError, GiteaAPIResponseError};
+
+impl Gitea {
+    /// Commits on `head` since its merge base with `base`.
+    pub async fn compare(
+        &self,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> Result<Compare, Box<dyn GiteaAPIError>> {
+        let endpoint = format!(
+            "{}/compare/{}...{}",
+            self.repo_path(repo),
+            encode(base),
+            encode(head)
+        );
+
+        match self.get(endpoint, None).await {
+            Ok(response) => parse(response, "comparison"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo or branch not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn list_commits(
+        &self,
+        repo: &str,
+        sha: &str,
+        path: Option<&str>,
+        limit: u8,
+    ) -> Result<Vec<Commit>, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/commits", self.repo_path(repo));
+        let limit = limit.to_string();
+        let mut params = vec![("sha", sha), ("limit", limit.as_str())];
+        if let Some(path) = path {
+            params.push(("path", path));
+        }
+
+        match self.get(endpoint, Some(&params)).await {
+            Ok(response) => parse(response, "commits"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Not found"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repository is empty"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn get_combined_status(
+        &self,
+        repo: &str,
+        reference: &str,
+    ) -> Result<CombinedStatus, Box<dyn GiteaAPIError>> {
+        let endpoint = format!(
+            "{}/commits/{}/status",
+            self.repo_path(repo),
+            encode(reference)
+        );
+
+        match self.get(endpoint, None).await {
+            Ok(response) => parse(response, "commit status"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Commit not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CommitUser {
+    pub login: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CommitActor {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct RepoCommit {
+    pub message: String,
+    pub author: Option<CommitActor>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CommitAffectedFile {
+    pub filename: String,
+    pub status: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Commit {
+    pub sha: String,
+    pub html_url: Option<String>,
+    pub commit: RepoCommit,
+    /// the account matching the commit author, when there is one
+    pub author: Option<CommitUser>,
+    pub files: Option<Vec<CommitAffectedFile>>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Compare {
+    pub total_commits: u64,
+    pub commits: Vec<Commit>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitStatusState {
+    Pending,
+    Success,
+    Error,
+    Failure,
+    Warning,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CommitStatus {
+    pub id: u64,
+    pub context: String,
+    pub status: CommitStatusState,
+    pub target_url: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CombinedStatus {
+    pub sha: String,
+    pub total_count: u64,
+    pub statuses: Option<Vec<CommitStatus>>,
+}
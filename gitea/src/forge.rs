@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use forge::{
+    Branch, BranchMerge, Check, CheckState, Commit, CompareStatus, Comparison, Forge, ForgeError,
+    MergeMethod, PullMerge, PullRequest, Repository,
+};
+
+use crate::commits::response::{self, CommitStatusState};
+use crate::pulls::response as pulls;
+use crate::{Gitea, GiteaAPIError};
+
+impl From<Box<dyn GiteaAPIError>> for ForgeError {
+    fn from(e: Box<dyn GiteaAPIError>) -> Self {
+        ForgeError {
+            message: e.error_message(),
+            extra_info: e.extra_info(),
+        }
+    }
+}
+
+fn to_commit(commit: &response::Commit) -> Commit {
+    Commit {
+        sha: commit.sha.clone(),
+        message: commit.commit.message.clone(),
+        author: match &commit.author {
+            Some(user) => Some(user.login.clone()),
+            None => commit
+                .commit
+                .author
+                .as_ref()
+                .map(|author| author.name.clone()),
+        },
+    }
+}
+
+fn to_pull_request(pr: &pulls::PullRequest) -> PullRequest {
+    PullRequest {
+        number: pr.number,
+        title: pr.title.clone(),
+        url: pr.html_url.clone(),
+        head: pr.head.reference.clone(),
+        base: pr.base.reference.clone(),
+        head_sha: pr.head.sha.clone(),
+        mergeable: Some(pr.mergeable),
+        // gitea doesn't tell why a pull request can't be merged
+        mergeable_state: None,
+    }
+}
+
+#[async_trait]
+impl Forge for Gitea {
+    async fn get_repo(&self, name: &str) -> Result<Repository, ForgeError> {
+        let repo = Gitea::get_repo(self, name).await?;
+        Ok(Repository {
+            name: repo.name,
+            full_name: repo.full_name,
+            web_url: repo.html_url,
+            clone_url: repo.clone_url,
+            default_branch: repo.default_branch,
+            archived: repo.archived.unwrap_or(false),
+        })
+    }
+
+    async fn list_branches(&self, repo: &Repository) -> Result<Vec<Branch>, ForgeError> {
+        let branches = Gitea::list_branches(self, &repo.name).await?;
+        Ok(branches
+            .iter()
+            .map(|branch| Branch {
+                name: branch.name.clone(),
+                sha: branch.commit.id.clone(),
+                protected: branch.protected,
+            })
+            .collect())
+    }
+
+    async fn compare_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+    ) -> Result<Comparison, ForgeError> {
+        // gitea only lists the commits on the head side of a comparison
+        let ahead = self.compare(&repo.name, head, base).await?;
+        let behind = self.compare(&repo.name, base, head).await?;
+
+        let mut files: Vec<String> = ahead
+            .commits
+            .iter()
+            .flat_map(|commit| commit.files.iter().flatten())
+            .map(|file| file.filename.clone())
+            .collect();
+        files.sort();
+        files.dedup();
+
+        Ok(Comparison {
+            status: match (ahead.total_commits, behind.total_commits) {
+                (0, 0) => CompareStatus::Identical,
+                (_, 0) => CompareStatus::Ahead,
+                (0, _) => CompareStatus::Behind,
+                _ => CompareStatus::Diverged,
+            },
+            ahead_by: ahead.total_commits,
+            behind_by: behind.total_commits,
+            commits: ahead.commits.iter().map(to_commit).collect(),
+            files,
+        })
+    }
+
+    async fn create_branch(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        from: &str,
+    ) -> Result<(), ForgeError> {
+        Gitea::create_branch(self, &repo.name, branch, from).await?;
+        Ok(())
+    }
+
+    async fn delete_branch(&self, repo: &Repository, branch: &str) -> Result<(), ForgeError> {
+        Ok(Gitea::delete_branch(self, &repo.name, branch).await?)
+    }
+
+    async fn merge_branches(
+        &self,
+        _repo: &Repository,
+        _base: &str,
+        _head: &str,
+        _commit_message: &str,
+    ) -> Result<BranchMerge, ForgeError> {
+        // there is no endpoint merging branches outside of a pull request
+        Err(ForgeError::unsupported("direct merges"))
+    }
+
+    async fn find_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let pulls = self.list_pulls(&repo.name).await?;
+        Ok(pulls
+            .iter()
+            .find(|pr| pr.head.reference == from && pr.base.reference == to)
+            .map(to_pull_request))
+    }
+
+    async fn get_pull(&self, repo: &Repository, number: u64) -> Result<PullRequest, ForgeError> {
+        Ok(to_pull_request(
+            &Gitea::get_pull(self, &repo.name, number).await?,
+        ))
+    }
+
+    async fn create_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+        title: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest, ForgeError> {
+        let pr = Gitea::create_pull(self, &repo.name, from, to, title, body).await?;
+        Ok(to_pull_request(&pr))
+    }
+
+    async fn merge_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+        method: MergeMethod,
+    ) -> Result<PullMerge, ForgeError> {
+        let style = match method {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        };
+        Gitea::merge_pull(
+            self,
+            &repo.name,
+            pull_request.number,
+            &pull_request.head_sha,
+            style,
+        )
+        .await?;
+        // the merge endpoint answers with an empty body
+        let pr = Gitea::get_pull(self, &repo.name, pull_request.number).await?;
+        Ok(PullMerge {
+            merged: pr.merged,
+            sha: pr.merge_commit_sha,
+            message: String::from(if pr.merged {
+                "Pull Request successfully merged"
+            } else {
+                "Pull Request not merged"
+            }),
+        })
+    }
+
+    async fn close_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        Gitea::close_pull(self, &repo.name, pull_request.number).await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+        reviewers: &[String],
+    ) -> Result<(), ForgeError> {
+        Gitea::request_reviewers(self, &repo.name, pull_request.number, reviewers).await?;
+        Ok(())
+    }
+
+    async fn update_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        Ok(Gitea::update_pull(self, &repo.name, pull_request.number).await?)
+    }
+
+    async fn list_checks(&self, repo: &Repository, sha: &str) -> Result<Vec<Check>, ForgeError> {
+        let status = self.get_combined_status(&repo.name, sha).await?;
+        Ok(status
+            .statuses
+            .iter()
+            .flatten()
+            .map(|status| Check {
+                name: status.context.clone(),
+                state: match status.status {
+                    CommitStatusState::Success | CommitStatusState::Warning => CheckState::Success,
+                    CommitStatusState::Error | CommitStatusState::Failure => CheckState::Failure,
+                    CommitStatusState::Pending => CheckState::Pending,
+                },
+            })
+            .collect())
+    }
+
+    async fn last_author(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        path: &str,
+    ) -> Result<Option<String>, ForgeError> {
+        let commits = self.list_commits(&repo.name, branch, Some(path), 1).await?;
+        Ok(commits.first().and_then(|commit| to_commit(commit).author))
+    }
+}
@@ -0,0 +1,7 @@
+pub struct Gitea {
+    pub client: reqwest::Client,
+    /// instance root, e.g. `https://codeberg.org`
+    pub url: String,
+    pub owner: String,
+    pub token: String,
+}
@@ -0,0 +1,204 @@
+pub mod branches;
+pub mod commits;
+mod forge;
+mod gitea;
+pub mod pulls;
+pub mod repos;
+
+pub use gitea::Gitea;
+use log::debug;
+
+use std::collections::HashMap;
+
+use reqwest::{header, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+pub trait GiteaAPIError {
+    fn error_message(&self) -> String;
+    fn extra_info(&self) -> Option<String>;
+}
+
+pub struct GiteaAPIResponseDeserializeError {
+    pub parse_error: String,
+    pub original_response: Option<String>,
+}
+
+pub struct GiteaAPIResponseError {
+    pub message: String,
+}
+
+impl GiteaAPIError for GiteaAPIResponseError {
+    fn error_message(&self) -> String {
+        self.message.clone()
+    }
+
+    fn extra_info(&self) -> Option<String> {
+        None
+    }
+}
+
+impl GiteaAPIError for GiteaAPIResponseDeserializeError {
+    fn error_message(&self) -> String {
+        self.parse_error.clone()
+    }
+
+    fn extra_info(&self) -> Option<String> {
+        self.original_response.clone()
+    }
+}
+
+/// Percent-encodes a path segment, branch names may contain slashes.
+fn encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Items asked for per page; Gitea may answer with fewer, down to its
+/// `MAX_RESPONSE_ITEMS`.
+const PER_PAGE: usize = 100;
+
+/// Why a list could not be read in full.
+enum PageError {
+    Status(reqwest::StatusCode),
+    Parse(Box<dyn GiteaAPIError>),
+}
+
+fn parse<T: DeserializeOwned>(response: String, what: &str) -> Result<T, Box<dyn GiteaAPIError>> {
+    let ds = &mut serde_json::Deserializer::from_str(&response);
+    let result: Result<T, _> = serde_path_to_error::deserialize(ds);
+    match result {
+        Ok(value) => Ok(value),
+        Err(e) => Err(Box::new(GiteaAPIResponseDeserializeError {
+            parse_error: format!("Unable to get {}: {}", what, e),
+            original_response: Some(response),
+        })),
+    }
+}
+
+impl Gitea {
+    pub fn new(url: String, token: String, owner: String) -> Gitea {
+        Gitea {
+            client: reqwest::Client::new(),
+            url,
+            owner,
+            token,
+        }
+    }
+
+    /// `repos/{owner}/{repo}`, the prefix of every repository endpoint.
+    fn repo_path(&self, repo: &str) -> String {
+        format!("repos/{}/{}", encode(&self.owner), encode(repo))
+    }
+
+    fn endpoint_url(&self, endpoint: String) -> String {
+        format!("{}/api/v1/{}", self.url.trim_end_matches('/'), endpoint)
+    }
+
+    fn add_headers(&self, req: RequestBuilder) -> RequestBuilder {
+        req.header(header::AUTHORIZATION, format!("token {}", self.token))
+            .header(header::USER_AGENT, "MultiGitRs")
+            .header(header::ACCEPT, "application/json")
+    }
+
+    async fn respond(&self, req: RequestBuilder) -> Result<Response, reqwest::StatusCode> {
+        let r: Response = req.send().await.unwrap();
+
+        if let Err(e) = r.error_for_status_ref() {
+            let response = r.text().await.unwrap();
+            debug!("error response: {:?}", response);
+            Err(e.status().unwrap())
+        } else {
+            Ok(r)
+        }
+    }
+
+    async fn send(&self, req: RequestBuilder) -> Result<String, reqwest::StatusCode> {
+        Ok(self.respond(req).await?.text().await.unwrap())
+    }
+
+    async fn get(
+        &self,
+        endpoint: String,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.get(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).query(&params)).await
+    }
+
+    /// Every page of a list. Gitea caps the page size at its own maximum, so
+    /// the `X-Total-Count` it answers with tells when the list is read; without
+    /// it, a short page is the last.
+    async fn get_pages<T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        params: &[(&str, &str)],
+        what: &str,
+    ) -> Result<Vec<T>, PageError> {
+        let limit = PER_PAGE.to_string();
+        let mut items = Vec::new();
+        for page in 1.. {
+            let page = page.to_string();
+            let mut query = params.to_vec();
+            query.push(("limit", &limit));
+            query.push(("page", &page));
+            let req = self.client.get(self.endpoint_url(endpoint.clone()));
+            let r = self
+                .respond(self.add_headers(req).query(&query))
+                .await
+                .map_err(PageError::Status)?;
+            let total: Option<usize> = r
+                .headers()
+                .get("x-total-count")
+                .and_then(|total| total.to_str().ok())
+                .and_then(|total| total.parse().ok());
+            let listed: Vec<T> = parse(r.text().await.unwrap(), what).map_err(PageError::Parse)?;
+            let short = listed.is_empty() || (total.is_none() && listed.len() < PER_PAGE);
+            items.extend(listed);
+            if short || total.is_some_and(|total| items.len() >= total) {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    async fn post(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<&str, &str>>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.post(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).json(&params)).await
+    }
+
+    async fn patch(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<&str, &str>>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.patch(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).json(&params)).await
+    }
+
+    /// Like [`Gitea::post`], for bodies that aren't only strings.
+    async fn post_json<T: serde::Serialize + ?Sized>(
+        &self,
+        endpoint: String,
+        body: &T,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.post(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).json(body)).await
+    }
+
+    async fn delete(&self, endpoint: String) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.delete(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req)).await
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use super::response::PullRequest;
+use crate::{parse, Gitea, GiteaAPIError, GiteaAPIResponseError, PageError};
+
+impl Gitea {
+    pub async fn list_pulls(&self, repo: &str) -> Result<Vec<PullRequest>, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls", self.repo_path(repo));
+
+        match self
+            .get_pages(endpoint, &[("state", "open")], "list of pull requests")
+            .await
+        {
+            Ok(pulls) => Ok(pulls),
+            Err(PageError::Parse(e)) => Err(e),
+            Err(PageError::Status(status_code)) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn get_pull(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequest, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls/{number}", self.repo_path(repo));
+
+        match self.get(endpoint, None).await {
+            Ok(response) => parse(response, "pull request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Pull Request not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn create_pull(
+        &self,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls", self.repo_path(repo));
+        let mut params = HashMap::with_capacity(4);
+        params.insert("head", head);
+        params.insert("base", base);
+        params.insert("title", title);
+        if let Some(body) = body {
+            params.insert("body", body);
+        }
+
+        match self.post(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "created pull request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("A pull request already exists for these branches"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Validation failed"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo or branch not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    /// Merges the pull request with `style` (`merge`, `squash` or `rebase`),
+    /// refusing if its head moved past `head_sha`.
+    pub async fn merge_pull(
+        &self,
+        repo: &str,
+        number: u64,
+        head_sha: &str,
+        style: &str,
+    ) -> Result<(), Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls/{number}/merge", self.repo_path(repo));
+        let mut params = HashMap::with_capacity(2);
+        params.insert("Do", style);
+        params.insert("head_commit_id", head_sha);
+
+        match self.post(endpoint, Some(params)).await {
+            Ok(_) => Ok(()),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::METHOD_NOT_ALLOWED => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Pull Request is not mergeable"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Head branch was modified, review and try again"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Pull Request not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn close_pull(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequest, Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls/{number}", self.repo_path(repo));
+        let params = HashMap::from([("state", "closed")]);
+
+        match self.patch(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "closed pull request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("You are not allowed to close this pull request"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Pull Request not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn request_reviewers(
+        &self,
+        repo: &str,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<(), Box<dyn GiteaAPIError>> {
+        let endpoint = format!(
+            "{}/pulls/{number}/requested_reviewers",
+            self.repo_path(repo)
+        );
+        let body = HashMap::from([("reviewers", reviewers)]);
+
+        match self.post_json(endpoint, &body).await {
+            Ok(_) => Ok(()),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Reviewers can't be requested"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Pull Request or reviewer not found"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    /// Merges the base branch into the pull request head branch.
+    pub async fn update_pull(&self, repo: &str, number: u64) -> Result<(), Box<dyn GiteaAPIError>> {
+        let endpoint = format!("{}/pulls/{number}/update?style=merge", self.repo_path(repo));
+
+        match self.post(endpoint, None).await {
+            Ok(_) => Ok(()),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("You are not allowed to push to the head branch"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Head branch conflicts with its base"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Head branch is already up to date"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
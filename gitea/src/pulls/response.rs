@@ -0,0 +1,31 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PullRequestState {
+    Open,
+    Closed,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct PullRequestBranch {
+    pub label: String,
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub sha: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct PullRequest {
+    pub id: u64,
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: PullRequestState,
+    pub html_url: String,
+    pub head: PullRequestBranch,
+    pub base: PullRequestBranch,
+    pub mergeable: bool,
+    pub merged: bool,
+    pub merge_commit_sha: Option<String>,
+}
@@ -0,0 +1,21 @@
+use super::response::Repository;
+use crate::{parse, Gitea, GiteaAPIError, GiteaAPIResponseError};
+
+impl Gitea {
+    pub async fn get_repo(&self, name: &str) -> Result<Repository, Box<dyn GiteaAPIError>> {
+        match self.get(self.repo_path(name), None).await {
+            Ok(response) => parse(response, "repo"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Repo not found"),
+                })),
+                reqwest::StatusCode::UNAUTHORIZED => Err(Box::new(GiteaAPIResponseError {
+                    message: String::from("Unauthorized, check the gitea token"),
+                })),
+                _ => Err(Box::new(GiteaAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use forge::{CheckState, CompareStatus, Forge, MergeMethod, Repository};
+use gitea::Gitea;
+use serde_json::{json, Value};
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const REPO: &str = "/api/v1/repos/mirror/api";
+
+fn gitea(server: &MockServer) -> Gitea {
+    Gitea::new(
+        server.uri(),
+        String::from("gitea-token"),
+        String::from("mirror"),
+    )
+}
+
+fn repository() -> Repository {
+    Repository {
+        name: String::from("api"),
+        full_name: String::from("mirror/api"),
+        web_url: String::from("https://forgejo.example.com/mirror/api"),
+        clone_url: String::from("https://forgejo.example.com/mirror/api.git"),
+        default_branch: Some(String::from("main")),
+        archived: false,
+    }
+}
+
+fn commit(sha: &str, author: &str, files: &[&str]) -> Value {
+    let files: Vec<Value> = files
+        .iter()
+        .map(|filename| json!({"filename": filename, "status": "modified"}))
+        .collect();
+    json!({
+        "url": format!("https://forgejo.example.com/api/v1/repos/mirror/api/git/commits/{}", sha),
+        "sha": sha,
+        "html_url": format!("https://forgejo.example.com/mirror/api/commit/{}", sha),
+        "commit": {
+            "message": format!("commit {}\n", sha),
+            "author": {"name": author, "email": "dev@example.com", "date": "2024-01-01T00:00:00Z"}
+        },
+        "author": null,
+        "files": files
+    })
+}
+
+fn pull_request(merged: bool, mergeable: bool) -> Value {
+    json!({
+        "id": 77,
+        "number": 5,
+        "title": "PR for: 3. main into prod",
+        "body": "",
+        "state": if merged { "closed" } else { "open" },
+        "html_url": "https://forgejo.example.com/mirror/api/pulls/5",
+        "head": {"label": "main", "ref": "main", "sha": "1111111111111111111111111111111111111111", "repo_id": 1},
+        "base": {"label": "prod", "ref": "prod", "sha": "3333333333333333333333333333333333333333", "repo_id": 1},
+        "mergeable": mergeable,
+        "merged": merged,
+        "merge_commit_sha": if merged { json!("2222222222222222222222222222222222222222") } else { Value::Null }
+    })
+}
+
+#[tokio::test]
+async fn get_repo_reads_the_owner_repo() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(REPO))
+        .and(header("Authorization", "token gitea-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1,
+            "name": "api",
+            "full_name": "mirror/api",
+            "html_url": "https://forgejo.example.com/mirror/api",
+            "clone_url": "https://forgejo.example.com/mirror/api.git",
+            "ssh_url": "git@forgejo.example.com:mirror/api.git",
+            "default_branch": "main",
+            "archived": false,
+            "mirror": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let repo = Forge::get_repo(&gitea(&server), "api").await.unwrap();
+
+    assert_eq!(repo.full_name, "mirror/api");
+    assert_eq!(repo.clone_url, "https://forgejo.example.com/mirror/api.git");
+    assert_eq!(repo.default_branch.as_deref(), Some("main"));
+}
+
+#[tokio::test]
+async fn branches_are_listed_created_and_deleted() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/branches", REPO)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "name": "main",
+                "commit": {"id": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "message": "init\n"},
+                "protected": false
+            },
+            {
+                "name": "prod",
+                "commit": {"id": "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb", "message": "init\n"},
+                "protected": true
+            }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/branches", REPO)))
+        .and(body_json(
+            json!({"new_branch_name": "release/3", "old_branch_name": "main"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "release/3",
+            "commit": {"id": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "message": "init\n"},
+            "protected": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("{}/branches/release%2F3", REPO)))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let gitea = gitea(&server);
+    let repo = repository();
+
+    let branches = Forge::list_branches(&gitea, &repo).await.unwrap();
+    assert_eq!(branches.len(), 2);
+    assert!(branches[1].protected);
+
+    Forge::create_branch(&gitea, &repo, "release/3", "main")
+        .await
+        .unwrap();
+    Forge::delete_branch(&gitea, &repo, "release/3")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn compare_branches_combines_both_sides() {
+    let server = MockServer::start().await;
+    // commits on prod missing from main
+    Mock::given(method("GET"))
+        .and(path(format!("{}/compare/main...prod", REPO)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_commits": 1,
+            "commits": [commit("cccccccccccccccccccccccccccccccccccccccc", "Grace", &["hotfix.txt"])]
+        })))
+        .mount(&server)
+        .await;
+    // nothing on main missing from prod
+    Mock::given(method("GET"))
+        .and(path(format!("{}/compare/prod...main", REPO)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"total_commits": 0, "commits": []})),
+        )
+        .mount(&server)
+        .await;
+
+    let comparison = gitea(&server)
+        .compare_branches(&repository(), "prod", "main")
+        .await
+        .unwrap();
+
+    assert_eq!(comparison.status, CompareStatus::Ahead);
+    assert_eq!(comparison.ahead_by, 1);
+    assert_eq!(comparison.behind_by, 0);
+    assert_eq!(comparison.commits[0].author.as_deref(), Some("Grace"));
+    assert_eq!(comparison.files, vec![String::from("hotfix.txt")]);
+}
+
+#[tokio::test]
+async fn pulls_are_opened_found_and_merged() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pulls", REPO)))
+        .and(query_param("state", "open"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/pulls", REPO)))
+        .and(body_json(json!({
+            "head": "main",
+            "base": "prod",
+            "title": "PR for: 3. main into prod"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(pull_request(false, true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/pulls/5/merge", REPO)))
+        .and(body_json(json!({
+            "Do": "squash",
+            "head_commit_id": "1111111111111111111111111111111111111111"
+        })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pulls/5", REPO)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pull_request(true, false)))
+        .mount(&server)
+        .await;
+
+    let gitea = gitea(&server);
+    let repo = repository();
+
+    assert!(gitea
+        .find_pull(&repo, "main", "prod")
+        .await
+        .unwrap()
+        .is_none());
+
+    let pr = Forge::create_pull(
+        &gitea,
+        &repo,
+        "main",
+        "prod",
+        "PR for: 3. main into prod",
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(pr.number, 5);
+    assert_eq!(pr.mergeable, Some(true));
+
+    let merge = Forge::merge_pull(&gitea, &repo, &pr, MergeMethod::Squash)
+        .await
+        .unwrap();
+    assert!(merge.merged);
+    assert_eq!(
+        merge.sha.as_deref(),
+        Some("2222222222222222222222222222222222222222")
+    );
+}
+
+#[tokio::test]
+async fn find_pull_matches_head_and_base() {
+    let server = MockServer::start().await;
+    let mut other = pull_request(false, true);
+    other["number"] = json!(4);
+    other["base"]["ref"] = json!("staging");
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pulls", REPO)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([other, pull_request(false, false)])),
+        )
+        .mount(&server)
+        .await;
+
+    let pr = gitea(&server)
+        .find_pull(&repository(), "main", "prod")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(pr.number, 5);
+    assert_eq!(pr.mergeable, Some(false));
+}
+
+#[tokio::test]
+async fn find_pull_reads_every_page() {
+    let server = MockServer::start().await;
+    // the server answers with fewer items than asked for
+    let other = |number: u64| {
+        let mut other = pull_request(false, true);
+        other["number"] = json!(number);
+        other["head"]["ref"] = json!(format!("feature/{}", number));
+        other
+    };
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pulls", REPO)))
+        .and(query_param("state", "open"))
+        .and(query_param("page", "1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Count", "3")
+                .set_body_json(json!([other(1), other(2)])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/pulls", REPO)))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Count", "3")
+                .set_body_json(json!([pull_request(false, true)])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let pr = gitea(&server)
+        .find_pull(&repository(), "main", "prod")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(pr.number, 5);
+}
+
+#[tokio::test]
+async fn unmergeable_pulls_are_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/pulls/5/merge", REPO)))
+        .respond_with(ResponseTemplate::new(405).set_body_json(json!({"message": "not mergeable"})))
+        .mount(&server)
+        .await;
+
+    let pr = forge::PullRequest {
+        number: 5,
+        title: String::from("PR for: 3. main into prod"),
+        url: String::from("https://forgejo.example.com/mirror/api/pulls/5"),
+        head: String::from("main"),
+        base: String::from("prod"),
+        head_sha: String::from("1111111111111111111111111111111111111111"),
+        mergeable: Some(false),
+        mergeable_state: None,
+    };
+    let error = Forge::merge_pull(&gitea(&server), &repository(), &pr, MergeMethod::Merge)
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.error_message(), "Pull Request is not mergeable");
+}
+
+#[tokio::test]
+async fn commit_statuses_map_to_checks() {
+    let server = MockServer::start().await;
+    let sha = "1111111111111111111111111111111111111111";
+    Mock::given(method("GET"))
+        .and(path(format!("{}/commits/{}/status", REPO, sha)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "state": "pending",
+            "sha": sha,
+            "total_count": 3,
+            "statuses": [
+                {"id": 1, "context": "ci/test", "status": "success", "target_url": null, "description": ""},
+                {"id": 2, "context": "ci/build", "status": "pending", "target_url": null, "description": ""},
+                {"id": 3, "context": "ci/lint", "status": "failure", "target_url": null, "description": ""}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let checks = gitea(&server)
+        .list_checks(&repository(), sha)
+        .await
+        .unwrap();
+
+    let states: Vec<CheckState> = checks.into_iter().map(|check| check.state).collect();
+    assert_eq!(
+        states,
+        vec![
+            CheckState::Success,
+            CheckState::Pending,
+            CheckState::Failure
+        ]
+    );
+}
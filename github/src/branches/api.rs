@@ -1,12 +1,15 @@
+use std::collections::HashMap;
+
+use crate::commits::response::Commit;
 use crate::repos::response::Repo;
 use crate::Github;
 use crate::{GithubAPIError, GithubAPIResponseDeserializeError, GithubAPIResponseError};
 
-use super::response::Branch;
+use super::response::{Branch, BranchMergeStatus};
 
 impl Github {
     pub async fn list_branches(&self, repo: &Repo) -> Result<Vec<Branch>, Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/branches", self.owner, repo.name);
+        let endpoint = format!("repos/{}/branches", repo.full_name);
 
         match self.get(endpoint, None).await {
             Ok(response) => {
@@ -30,4 +33,50 @@ impl Github {
             },
         }
     }
+
+    pub async fn merge_branches(
+        &self,
+        repo: &Repo,
+        base: &String,
+        head: &String,
+        commit_message: &String,
+    ) -> Result<BranchMergeStatus, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/merges", repo.full_name);
+        let mut params = HashMap::<String, &String>::with_capacity(3);
+        params.insert(String::from("base"), base);
+        params.insert(String::from("head"), head);
+        params.insert(String::from("commit_message"), commit_message);
+
+        match self.post_with_status(endpoint, Some(params)).await {
+            Ok((reqwest::StatusCode::NO_CONTENT, _)) => Ok(BranchMergeStatus::NothingToMerge),
+            Ok((_, response)) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<Commit, _> = serde_path_to_error::deserialize(ds);
+                match result {
+                    Ok(commit) => Ok(BranchMergeStatus::Merged(Box::new(commit))),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!("Unable to parse merge commit: {}", e),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::CONFLICT => Ok(BranchMergeStatus::Conflict),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("Base or head branch not found"),
+                })),
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("You are not allowed to merge into this branch"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GithubAPIResponseError {
+                        message: String::from("Validation error"),
+                    }))
+                }
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
 }
@@ -17,3 +17,13 @@ pub struct Branch {
 pub struct BranchProtection {
     pub required_status_checks: Value,
 }
+
+#[derive(Debug)]
+pub enum BranchMergeStatus {
+    /// `head` was merged into `base`, holds the resulting merge commit
+    Merged(Box<Commit>),
+    /// `base` already contains `head`
+    NothingToMerge,
+    /// `head` can't be merged into `base` without resolving conflicts
+    Conflict,
+}
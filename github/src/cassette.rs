@@ -0,0 +1,232 @@
+//! Record and replay of the exchanges with the GitHub API.
+//!
+//! A recording cassette saves every request and its response to a JSON file,
+//! a replaying one answers the requests from that file without touching the
+//! network. Headers are not recorded, and the token and anything shaped like
+//! a GitHub token are redacted from what is.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use reqwest::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+const REDACTED: &str = "<redacted>";
+/// prefixes of GitHub's personal, OAuth, app and refresh tokens
+const TOKEN_PREFIXES: [&str; 6] = ["github_pat_", "ghp_", "gho_", "ghu_", "ghs_", "ghr_"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// endpoint and query, relative to the API root
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedResponse {
+    pub status: u16,
+    /// JSON bodies are kept as JSON so cassettes can be read and diffed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    /// anything else, including empty bodies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct CassetteFile {
+    pub interactions: Vec<Interaction>,
+}
+
+impl CassetteFile {
+    pub fn load(path: &Path) -> Result<CassetteFile, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read cassette {}: {}", path.display(), e))?;
+        let ds = &mut serde_json::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(ds)
+            .map_err(|e| format!("Unable to load cassette {}: {}", path.display(), e))
+    }
+}
+
+enum Mode {
+    Record(PathBuf),
+    /// recorded interactions, and whether each one was already played
+    Replay(Vec<(Interaction, bool)>),
+}
+
+pub struct Cassette {
+    mode: Mutex<Mode>,
+    recorded: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    /// Records to `path`, the file is rewritten after each exchange.
+    pub fn record(path: &Path) -> Cassette {
+        Cassette {
+            mode: Mutex::new(Mode::Record(path.to_path_buf())),
+            recorded: Mutex::new(vec![]),
+        }
+    }
+
+    /// Serves the exchanges recorded in `path`. Identical requests are
+    /// answered in the order they were recorded.
+    pub fn replay(path: &Path) -> Result<Cassette, String> {
+        let file = CassetteFile::load(path)?;
+        Ok(Cassette {
+            mode: Mutex::new(Mode::Replay(
+                file.interactions
+                    .into_iter()
+                    .map(|interaction| (interaction, false))
+                    .collect(),
+            )),
+            recorded: Mutex::new(vec![]),
+        })
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(*self.mode.lock().unwrap(), Mode::Replay(_))
+    }
+
+    /// The recorded answer to `request`, an error when nothing left in the
+    /// cassette matches it.
+    pub(crate) fn play(&self, request: &RecordedRequest) -> Result<(StatusCode, String), String> {
+        let mut mode = self.mode.lock().unwrap();
+        let interactions = match &mut *mode {
+            Mode::Replay(interactions) => interactions,
+            Mode::Record(_) => return Err(String::from("Cassette is recording, not replaying")),
+        };
+        let (interaction, played) = interactions
+            .iter_mut()
+            .find(|(interaction, played)| !played && &interaction.request == request)
+            .ok_or_else(|| {
+                format!(
+                    "No recorded interaction left for {} {}",
+                    request.method, request.path
+                )
+            })?;
+        *played = true;
+
+        let response = &interaction.response;
+        let body = match (&response.json, &response.text) {
+            (Some(json), _) => json.to_string(),
+            (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        };
+        Ok((
+            StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            body,
+        ))
+    }
+
+    pub(crate) fn save(
+        &self,
+        request: RecordedRequest,
+        status: StatusCode,
+        body: &str,
+        token: &str,
+    ) {
+        let path = match &*self.mode.lock().unwrap() {
+            Mode::Record(path) => path.clone(),
+            Mode::Replay(_) => return,
+        };
+        let body = redact(body, token);
+        let response = match serde_json::from_str::<Value>(&body) {
+            Ok(json) => RecordedResponse {
+                status: status.as_u16(),
+                json: Some(json),
+                text: None,
+            },
+            Err(_) => RecordedResponse {
+                status: status.as_u16(),
+                json: None,
+                text: Some(body),
+            },
+        };
+
+        let mut recorded = self.recorded.lock().unwrap();
+        recorded.push(Interaction { request, response });
+        let file = CassetteFile {
+            interactions: recorded.clone(),
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let written = serde_json::to_string_pretty(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|text| std::fs::write(&path, text).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            log::error!("Unable to write cassette {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// What a cassette keeps of `request`, `None` if it didn't target `api_url`.
+pub(crate) fn recorded_request(
+    api_url: &str,
+    request: &reqwest::Request,
+    token: &str,
+) -> Option<RecordedRequest> {
+    let path = request
+        .url()
+        .as_str()
+        .strip_prefix(api_url)?
+        .trim_start_matches('/');
+    // the client sends `null` when it has no parameters
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|bytes| redact(&String::from_utf8_lossy(bytes), token))
+        .and_then(|body| serde_json::from_str::<Value>(&body).ok())
+        .filter(|body| !body.is_null());
+
+    Some(RecordedRequest {
+        method: request.method().to_string(),
+        path: redact(path, token),
+        body,
+    })
+}
+
+fn redact(text: &str, token: &str) -> String {
+    let text = if token.is_empty() {
+        text.to_string()
+    } else {
+        text.replace(token, REDACTED)
+    };
+    redact_tokens(&text)
+}
+
+/// `text` with the tokens it holds besides the client's own, e.g. a token
+/// echoed by a command or installation access tokens, redacted.
+fn redact_tokens(text: &str) -> String {
+    let is_token_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, prefix)) = TOKEN_PREFIXES
+        .iter()
+        .filter_map(|prefix| rest.find(prefix).map(|start| (start, prefix)))
+        .min()
+    {
+        let after = &rest[start + prefix.len()..];
+        let len = after.find(|c| !is_token_char(c)).unwrap_or(after.len());
+        // tokens start a word and are long, `my_ghp_x` isn't one
+        let starts_word = !rest[..start].ends_with(is_token_char);
+        redacted.push_str(&rest[..start]);
+        if starts_word && len >= 20 {
+            redacted.push_str(REDACTED);
+        } else {
+            redacted.push_str(&rest[start..start + prefix.len() + len]);
+        }
+        rest = &after[len..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
@@ -0,0 +1,64 @@
+use super::response::CheckRuns;
+use crate::repos::response::Repo;
+use crate::Github;
+use crate::GithubAPIError;
+use crate::GithubAPIResponseDeserializeError;
+use crate::GithubAPIResponseError;
+
+const PER_PAGE: usize = 100;
+
+impl Github {
+    /// Every check run of `reference`, `PER_PAGE` at a time.
+    pub async fn list_check_runs(
+        &self,
+        repo: &Repo,
+        reference: &String,
+    ) -> Result<CheckRuns, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/commits/{reference}/check-runs", repo.full_name);
+        let per_page = PER_PAGE.to_string();
+        let mut runs = CheckRuns {
+            total_count: 0,
+            check_runs: vec![],
+        };
+
+        for page in 1.. {
+            let page = page.to_string();
+            let query = [
+                (&String::from("per_page"), &per_page),
+                (&String::from("page"), &page),
+            ];
+            let listed = match self.get(endpoint.clone(), Some(&query)).await {
+                Ok(response) => {
+                    let ds = &mut serde_json::Deserializer::from_str(&response);
+                    let result: Result<CheckRuns, _> = serde_path_to_error::deserialize(ds);
+                    match result {
+                        Ok(check_runs) => check_runs,
+                        Err(e) => {
+                            return Err(Box::new(GithubAPIResponseDeserializeError {
+                                parse_error: format!("Unable to get check runs: {}", e),
+                                original_response: Some(response),
+                            }))
+                        }
+                    }
+                }
+                Err(status_code) => {
+                    return match status_code {
+                        reqwest::StatusCode::NOT_FOUND => Err(Box::new(GithubAPIResponseError {
+                            message: String::from("Not found"),
+                        })),
+                        _ => Err(Box::new(GithubAPIResponseError {
+                            message: format!("Unhandled: {}", status_code),
+                        })),
+                    }
+                }
+            };
+            let last = listed.check_runs.len() < PER_PAGE;
+            runs.total_count = listed.total_count;
+            runs.check_runs.extend(listed.check_runs);
+            if last || runs.check_runs.len() as u64 >= runs.total_count {
+                break;
+            }
+        }
+        Ok(runs)
+    }
+}
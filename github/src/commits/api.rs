@@ -1,4 +1,4 @@
-use super::response::CommitsComparison;
+use super::response::{Commit, CommitsComparison};
 use crate::repos::response::Repo;
 use crate::Github;
 use crate::GithubAPIError;
@@ -12,10 +12,7 @@ impl Github {
         base: &String,
         head: &String,
     ) -> Result<CommitsComparison, Box<dyn GithubAPIError>> {
-        let endpoint = format!(
-            "repos/{}/{}/compare/{}...{}",
-            self.owner, repo.name, head, base
-        );
+        let endpoint = format!("repos/{}/compare/{}...{}", repo.full_name, head, base);
 
         match self.get(endpoint, None).await {
             Ok(response) => {
@@ -45,4 +42,49 @@ impl Github {
             },
         }
     }
+
+    pub async fn list_commits(
+        &self,
+        repo: &Repo,
+        sha: &str,
+        path: Option<&str>,
+        per_page: u8,
+    ) -> Result<Vec<Commit>, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/commits", repo.full_name);
+        let per_page = per_page.to_string();
+        let mut params = vec![
+            (String::from("sha"), sha.to_string()),
+            (String::from("per_page"), per_page),
+        ];
+        if let Some(path) = path {
+            params.push((String::from("path"), path.to_string()));
+        }
+        let params: Vec<(&String, &String)> = params.iter().map(|(k, v)| (k, v)).collect();
+
+        match self.get(endpoint, Some(&params)).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<Vec<Commit>, _> = serde_path_to_error::deserialize(ds);
+
+                match result {
+                    Ok(commits) => Ok(commits),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!("Unable to get commits: {:?}", e),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("Not found"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("Repository is empty"),
+                })),
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("Unhandled"),
+                })),
+            },
+        }
+    }
 }
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
 use crate::response::GithubDiffEntry;
 use crate::users::response::User;
-use serde_derive::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
@@ -14,7 +16,13 @@ pub enum CompareStatus {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CommitsComparison {
     pub status: CompareStatus,
-    // TODO
+    pub html_url: Option<String>,
+    pub ahead_by: u64,
+    pub behind_by: u64,
+    pub total_commits: u64,
+    pub commits: Vec<Commit>,
+    pub merge_base_commit: Option<Commit>,
+    pub files: Option<Vec<GithubDiffEntry>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -24,7 +32,7 @@ pub struct Commit {
     pub node_id: Option<String>,
     pub html_url: Option<String>,
     pub comments_url: Option<String>,
-    pub commit: Option<Box<Commit>>,
+    pub commit: Option<GitCommit>,
     pub author: Option<User>,
     pub committer: Option<User>,
     pub parents: Option<Vec<ParentCommit>>,
@@ -45,3 +53,18 @@ pub struct CommitStats {
     pub deletions: u64,
     pub total: u64,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GitCommit {
+    pub url: Option<String>,
+    pub message: String,
+    pub author: Option<GitActor>,
+    pub committer: Option<GitActor>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GitActor {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub date: Option<DateTime<Utc>>,
+}
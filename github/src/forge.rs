@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use forge::{
+    Branch, BranchMerge, Check, CheckState, Commit, CompareStatus, Comparison, Forge, ForgeError,
+    ListedRepo, MergeMethod, PullMerge, PullRequest, Release, Repository, Review, ReviewState,
+};
+
+use crate::branches::response::BranchMergeStatus;
+use crate::checks::response::{CheckRunConclusion, CheckRunStatus};
+use crate::commits::response;
+use crate::pulls::response as pulls;
+use crate::releases::response as releases;
+use crate::repos::response::Repo;
+use crate::reviews::response as reviews;
+use crate::{Github, GithubAPIError};
+
+impl From<Box<dyn GithubAPIError>> for ForgeError {
+    fn from(e: Box<dyn GithubAPIError>) -> Self {
+        ForgeError {
+            message: e.error_message(),
+            extra_info: e.extra_info(),
+        }
+    }
+}
+
+fn to_repository(repo: &Repo) -> Repository {
+    Repository {
+        name: repo.name.clone(),
+        full_name: repo.full_name.clone(),
+        web_url: repo.html_url.clone(),
+        clone_url: format!("{}.git", repo.html_url),
+        default_branch: repo.default_branch.clone(),
+        archived: repo.archived || repo.disabled,
+    }
+}
+
+fn to_commit(commit: &response::Commit) -> Commit {
+    let git_commit = commit.commit.as_ref();
+    Commit {
+        sha: commit.sha.clone(),
+        message: git_commit
+            .map(|git_commit| git_commit.message.clone())
+            .unwrap_or_default(),
+        author: match &commit.author {
+            Some(user) => Some(user.login.clone()),
+            None => git_commit
+                .and_then(|git_commit| git_commit.author.as_ref())
+                .and_then(|author| author.name.clone()),
+        },
+    }
+}
+
+fn to_pull_request(pr: &pulls::PullRequest) -> PullRequest {
+    PullRequest {
+        number: pr.number,
+        title: pr.title.clone(),
+        url: pr.html_url.clone(),
+        head: pr.head.reference.clone(),
+        base: pr.base.reference.clone(),
+        head_sha: pr.head.sha.clone(),
+        mergeable: pr.mergeable,
+        mergeable_state: pr.mergeable_state.clone(),
+    }
+}
+
+fn to_release(release: &releases::Release) -> Release {
+    Release {
+        tag: release.tag_name.clone(),
+        url: release.html_url.clone(),
+        draft: release.draft,
+    }
+}
+
+/// [`Forge`] over the GitHub REST API.
+pub struct GithubForge {
+    pub gh: Github,
+    // the API endpoints take a `Repo`, keep the ones already fetched around
+    repos: Mutex<HashMap<String, Repo>>,
+}
+
+impl GithubForge {
+    pub fn new(gh: Github) -> GithubForge {
+        GithubForge {
+            gh,
+            repos: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch_repo(&self, name: &str) -> Result<Repo, ForgeError> {
+        let cached = self.repos.lock().unwrap().get(name).cloned();
+        if let Some(repo) = cached {
+            return Ok(repo);
+        }
+
+        let repo = self.gh.get_repo(&name.to_string()).await?;
+        self.repos
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), repo.clone());
+        Ok(repo)
+    }
+}
+
+#[async_trait]
+impl Forge for GithubForge {
+    async fn get_repo(&self, name: &str) -> Result<Repository, ForgeError> {
+        Ok(to_repository(&self.fetch_repo(name).await?))
+    }
+
+    async fn list_repos(&self) -> Result<Vec<ListedRepo>, ForgeError> {
+        let repos = self.gh.list_owner_repos().await?;
+        let mut cache = self.repos.lock().unwrap();
+        Ok(repos
+            .into_iter()
+            .map(|repo| {
+                let listed = ListedRepo {
+                    name: repo.name.clone(),
+                    archived: repo.archived || repo.disabled,
+                    fork: repo.fork,
+                    topics: repo.topics.clone(),
+                    language: repo.language.clone(),
+                };
+                cache.insert(repo.name.clone(), repo);
+                listed
+            })
+            .collect())
+    }
+
+    async fn list_team_repos(&self, team: &str) -> Result<Vec<String>, ForgeError> {
+        let repos = self.gh.list_team_repos(team).await?;
+        Ok(repos.into_iter().map(|repo| repo.name).collect())
+    }
+
+    async fn list_branches(&self, repo: &Repository) -> Result<Vec<Branch>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let branches = self.gh.list_branches(&repo).await?;
+        Ok(branches
+            .iter()
+            .map(|branch| Branch {
+                name: branch.name.clone(),
+                sha: branch.commit.sha.clone(),
+                protected: branch.protected,
+            })
+            .collect())
+    }
+
+    async fn compare_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+    ) -> Result<Comparison, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let comparison = self
+            .gh
+            .compare_branches(&repo, &base.to_string(), &head.to_string())
+            .await?;
+
+        Ok(Comparison {
+            status: match comparison.status {
+                response::CompareStatus::Ahead => CompareStatus::Ahead,
+                response::CompareStatus::Behind => CompareStatus::Behind,
+                response::CompareStatus::Diverged => CompareStatus::Diverged,
+                response::CompareStatus::Identical => CompareStatus::Identical,
+            },
+            ahead_by: comparison.ahead_by,
+            behind_by: comparison.behind_by,
+            commits: comparison.commits.iter().map(to_commit).collect(),
+            files: comparison
+                .files
+                .iter()
+                .flatten()
+                .map(|file| file.filename.clone())
+                .collect(),
+        })
+    }
+
+    async fn create_branch(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        from: &str,
+    ) -> Result<(), ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let from_ref = self.gh.get_reference(&repo, &from.to_string()).await?;
+        Ok(self
+            .gh
+            .create_reference(&repo, &branch.to_string(), &from_ref)
+            .await?)
+    }
+
+    async fn delete_branch(&self, repo: &Repository, branch: &str) -> Result<(), ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        Ok(self
+            .gh
+            .delete_reference(&repo, &format!("heads/{}", branch))
+            .await?)
+    }
+
+    async fn merge_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+        commit_message: &str,
+    ) -> Result<BranchMerge, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let merge_status = self
+            .gh
+            .merge_branches(
+                &repo,
+                &base.to_string(),
+                &head.to_string(),
+                &commit_message.to_string(),
+            )
+            .await?;
+
+        Ok(match merge_status {
+            BranchMergeStatus::Merged(commit) => BranchMerge::Merged(commit.sha),
+            BranchMergeStatus::NothingToMerge => BranchMerge::NothingToMerge,
+            BranchMergeStatus::Conflict => BranchMerge::Conflict,
+        })
+    }
+
+    async fn find_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let pulls = self
+            .gh
+            .list_pulls(&repo, &from.to_string(), &to.to_string())
+            .await?;
+
+        let existing_pr = pulls.iter().find(|pr| {
+            pr.head.label == format!("{}:{}", repo.owner.login, from)
+                && pr.base.label == format!("{}:{}", repo.owner.login, to)
+        });
+
+        match existing_pr {
+            // listed pull requests don't carry their mergeability
+            Some(pr) => {
+                let pr = self.gh.get_pull(&repo, pr.number).await?;
+                Ok(Some(to_pull_request(&pr)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_pull(&self, repo: &Repository, number: u64) -> Result<PullRequest, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        Ok(to_pull_request(&self.gh.get_pull(&repo, number).await?))
+    }
+
+    async fn create_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+        title: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let body = body.map(|body| body.to_string());
+        let pr = self
+            .gh
+            .open_pull(
+                &repo,
+                &from.to_string(),
+                &to.to_string(),
+                &title.to_string(),
+                body.as_ref(),
+            )
+            .await?;
+        Ok(to_pull_request(&pr))
+    }
+
+    async fn merge_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+        method: MergeMethod,
+    ) -> Result<PullMerge, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let pr = self.gh.get_pull(&repo, pull_request.number).await?;
+        let method = match method {
+            MergeMethod::Merge => pulls::MergeMethod::Merge,
+            MergeMethod::Squash => pulls::MergeMethod::Squash,
+            MergeMethod::Rebase => pulls::MergeMethod::Rebase,
+        };
+        let merge_status = self.gh.merge_pull(&repo, &pr, &method).await?;
+        Ok(PullMerge {
+            merged: merge_status.merged,
+            sha: Some(merge_status.sha),
+            message: merge_status.message,
+        })
+    }
+
+    async fn close_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let pr = self.gh.get_pull(&repo, pull_request.number).await?;
+        self.gh.close_pull(&repo, &pr).await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+        reviewers: &[String],
+    ) -> Result<(), ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let pr = self.gh.get_pull(&repo, pull_request.number).await?;
+        self.gh.request_reviewers(&repo, &pr, reviewers).await?;
+        Ok(())
+    }
+
+    async fn update_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let pr = self.gh.get_pull(&repo, pull_request.number).await?;
+        self.gh
+            .update_pull_branch(&repo, &pr, &pull_request.head_sha)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_checks(&self, repo: &Repository, sha: &str) -> Result<Vec<Check>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let runs = self.gh.list_check_runs(&repo, &sha.to_string()).await?;
+        Ok(runs
+            .check_runs
+            .iter()
+            .map(|run| Check {
+                name: run.name.clone(),
+                state: match (&run.status, &run.conclusion) {
+                    (CheckRunStatus::Completed, Some(CheckRunConclusion::Success))
+                    | (CheckRunStatus::Completed, Some(CheckRunConclusion::Neutral))
+                    | (CheckRunStatus::Completed, Some(CheckRunConclusion::Skipped)) => {
+                        CheckState::Success
+                    }
+                    (CheckRunStatus::Completed, _) => CheckState::Failure,
+                    _ => CheckState::Pending,
+                },
+            })
+            .collect())
+    }
+
+    async fn list_reviews(
+        &self,
+        repo: &Repository,
+        number: u64,
+    ) -> Result<Vec<Review>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let reviews = self.gh.list_reviews(&repo, number).await?;
+        Ok(reviews
+            .iter()
+            .filter_map(|review| {
+                let state = match review.state {
+                    reviews::ReviewState::Approved => ReviewState::Approved,
+                    reviews::ReviewState::ChangesRequested => ReviewState::ChangesRequested,
+                    reviews::ReviewState::Commented => ReviewState::Commented,
+                    reviews::ReviewState::Dismissed => ReviewState::Dismissed,
+                    reviews::ReviewState::Pending => ReviewState::Pending,
+                    reviews::ReviewState::Unknown => return None,
+                };
+                Some(Review {
+                    author: review.user.as_ref().map(|user| user.login.clone()),
+                    state,
+                })
+            })
+            .collect())
+    }
+
+    async fn find_release(
+        &self,
+        repo: &Repository,
+        tag: &str,
+    ) -> Result<Option<Release>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let release = self.gh.get_release_by_tag(&repo, tag).await?;
+        Ok(release.as_ref().map(to_release))
+    }
+
+    async fn create_release(
+        &self,
+        repo: &Repository,
+        tag: &str,
+        target: &str,
+        name: &str,
+        draft: bool,
+    ) -> Result<Release, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let release = self
+            .gh
+            .create_release(
+                &repo,
+                &releases::NewRelease {
+                    tag_name: tag,
+                    target_commitish: target,
+                    name,
+                    draft,
+                },
+            )
+            .await?;
+        Ok(to_release(&release))
+    }
+
+    async fn last_author(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        path: &str,
+    ) -> Result<Option<String>, ForgeError> {
+        let repo = self.fetch_repo(&repo.name).await?;
+        let commits = self.gh.list_commits(&repo, branch, Some(path), 1).await?;
+        Ok(commits.first().and_then(|commit| to_commit(commit).author))
+    }
+}
@@ -1,5 +1,17 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::cassette::Cassette;
+
 pub struct Github {
     pub client: reqwest::Client,
+    pub api_url: String,
     pub owner: String,
     pub token: String,
+    pub cassette: Option<Cassette>,
+    /// minimum delay between two mutating requests
+    pub write_interval: Duration,
+    pub(crate) last_write: Mutex<Option<Instant>>,
 }
@@ -1,20 +1,32 @@
 pub mod branches;
+pub mod cassette;
+pub mod checks;
 pub mod commits;
+mod forge;
 mod github;
 pub mod orgs;
 pub mod pulls;
 pub mod references;
+pub mod releases;
 pub mod repos;
 mod response;
+pub mod reviews;
 pub mod teams;
 pub mod users;
 
+pub use self::cassette::Cassette;
+pub use self::forge::GithubForge;
 pub use github::Github;
-use log::debug;
+use log::{debug, error};
 
 use std::collections::HashMap;
+use std::time::Duration;
 
-use reqwest::{header, RequestBuilder, Response};
+use reqwest::{header, Method, RequestBuilder, Response};
+use tokio::sync::Mutex;
+use tokio::time::{sleep_until, Instant};
+
+use cassette::recorded_request;
 
 pub trait GithubAPIError {
     fn error_message(&self) -> String;
@@ -54,35 +66,104 @@ impl Github {
     pub fn new(token: String, owner: String) -> Github {
         Github {
             client: reqwest::Client::new(),
+            api_url: String::from("https://api.github.com"),
             token,
             owner,
+            cassette: None,
+            write_interval: Duration::from_secs(1),
+            last_write: Mutex::new(None),
         }
     }
+
+    /// Points the client to another API root, e.g. a GitHub Enterprise
+    /// server (`https://github.example.com/api/v3`).
+    pub fn with_api_url(mut self, api_url: String) -> Github {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Records the exchanges to, or replays them from, `cassette`.
+    pub fn with_cassette(mut self, cassette: Cassette) -> Github {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Changes the delay kept between two mutating requests.
+    pub fn with_write_interval(mut self, write_interval: Duration) -> Github {
+        self.write_interval = write_interval;
+        self
+    }
+
     fn add_headers(&self, req: RequestBuilder) -> RequestBuilder {
         req.header(header::AUTHORIZATION, format!("token {}", self.token))
             .header(header::USER_AGENT, "MultiGitRs")
             .header(header::ACCEPT, "application/vnd.github+json")
     }
 
-    async fn send_and_parse(&self, req: RequestBuilder) -> Result<String, reqwest::StatusCode> {
-        let r: Response = req.send().await.unwrap();
-
-        if let Err(e) = r.error_for_status_ref() {
-            let response = r.text().await.unwrap();
+    async fn send(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<(reqwest::StatusCode, String), reqwest::StatusCode> {
+        let req = req.build().unwrap();
+        let recorded = self
+            .cassette
+            .as_ref()
+            .and_then(|_| recorded_request(&self.api_url, &req, &self.token));
+
+        let (status, response) = match (&self.cassette, recorded) {
+            (Some(cassette), Some(recorded)) if cassette.is_replay() => {
+                match cassette.play(&recorded) {
+                    Ok(played) => played,
+                    Err(e) => {
+                        // answered as a server error, the request fails like any other
+                        error!("{}", e);
+                        return Err(reqwest::StatusCode::NOT_IMPLEMENTED);
+                    }
+                }
+            }
+            (cassette, recorded) => {
+                // GitHub's secondary rate limits ask for mutating requests to be
+                // made one at a time, a second apart, so concurrent callers queue here
+                let mut last_write = if req.method() != Method::GET {
+                    let last_write = self.last_write.lock().await;
+                    if let Some(last) = *last_write {
+                        sleep_until(last + self.write_interval).await;
+                    }
+                    Some(last_write)
+                } else {
+                    None
+                };
+                let r: Response = self.client.execute(req).await.unwrap();
+                let status = r.status();
+                let response = r.text().await.unwrap();
+                if let Some(last_write) = last_write.as_mut() {
+                    **last_write = Some(Instant::now());
+                }
+                if let (Some(cassette), Some(recorded)) = (cassette, recorded) {
+                    cassette.save(recorded, status, &response, &self.token);
+                }
+                (status, response)
+            }
+        };
+
+        if status.is_client_error() || status.is_server_error() {
             debug!("error response: {:?}", response);
-            Err(e.status().unwrap())
+            Err(status)
         } else {
-            let response = r.text().await.unwrap();
-            Ok(response)
+            Ok((status, response))
         }
     }
 
+    async fn send_and_parse(&self, req: RequestBuilder) -> Result<String, reqwest::StatusCode> {
+        self.send(req).await.map(|(_, response)| response)
+    }
+
     async fn get(
         &self,
         endpoint: String,
         params: Option<&[(&String, &String)]>,
     ) -> Result<String, reqwest::StatusCode> {
-        let url = format!("https://api.github.com/{}", endpoint);
+        let url = format!("{}/{}", self.api_url, endpoint);
 
         let req = self.client.get(url);
 
@@ -95,30 +176,66 @@ impl Github {
         endpoint: String,
         params: Option<HashMap<String, &String>>,
     ) -> Result<String, reqwest::StatusCode> {
-        let url = format!("https://api.github.com/{}", endpoint);
+        let url = format!("{}/{}", self.api_url, endpoint);
 
         let req = self.client.post(url);
         self.send_and_parse(self.add_headers(req).json(&params))
             .await
     }
 
+    async fn post_with_status(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<String, &String>>,
+    ) -> Result<(reqwest::StatusCode, String), reqwest::StatusCode> {
+        let url = format!("{}/{}", self.api_url, endpoint);
+
+        let req = self.client.post(url);
+        self.send(self.add_headers(req).json(&params)).await
+    }
+
     async fn put(
         &self,
         endpoint: String,
         params: Option<HashMap<String, &String>>,
     ) -> Result<String, reqwest::StatusCode> {
-        let url = format!("https://api.github.com/{}", endpoint);
+        let url = format!("{}/{}", self.api_url, endpoint);
 
         let req = self.client.put(url);
         self.send_and_parse(self.add_headers(req).json(&params))
             .await
     }
+
+    async fn patch(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<String, &String>>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let url = format!("{}/{}", self.api_url, endpoint);
+
+        let req = self.client.patch(url);
+        self.send_and_parse(self.add_headers(req).json(&params))
+            .await
+    }
+
+    /// Like [`Github::post`], for bodies that aren't only strings.
+    async fn post_json<T: serde::Serialize + ?Sized>(
+        &self,
+        endpoint: String,
+        body: &T,
+    ) -> Result<String, reqwest::StatusCode> {
+        let url = format!("{}/{}", self.api_url, endpoint);
+
+        let req = self.client.post(url);
+        self.send_and_parse(self.add_headers(req).json(body)).await
+    }
+
     async fn delete(
         &self,
         endpoint: String,
         params: Option<HashMap<String, &String>>,
     ) -> Result<String, reqwest::StatusCode> {
-        let url = format!("https://api.github.com/{}", endpoint);
+        let url = format!("{}/{}", self.api_url, endpoint);
 
         let req = self.client.delete(url);
         self.send_and_parse(self.add_headers(req).json(&params))
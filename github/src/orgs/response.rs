@@ -9,13 +9,13 @@ pub struct Org {
     pub id: u64,
     pub node_id: String,
     pub url: String,
-    pub repos_url: String,
-    pub events_url: String,
-    pub hooks_url: String,
-    pub issues_url: String,
+    pub repos_url: Option<String>,
+    pub events_url: Option<String>,
+    pub hooks_url: Option<String>,
+    pub issues_url: Option<String>,
     pub memebers_url: Option<String>,
     pub public_members_url: Option<String>,
-    pub avatar_url: String,
+    pub avatar_url: Option<String>,
     pub description: Option<String>,
     pub name: Option<String>,
     pub company: Option<String>,
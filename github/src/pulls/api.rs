@@ -1,4 +1,6 @@
-use super::response::{PullRequest, PullRequestMergeStatus};
+use super::response::{
+    MergeMethod, PullRequest, PullRequestMergeStatus, PullRequestUpdateBranchStatus,
+};
 use crate::repos::response::Repo;
 use crate::Github;
 use crate::GithubAPIError;
@@ -12,7 +14,7 @@ impl Github {
         repo: &Repo,
         number: u64,
     ) -> Result<PullRequest, Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/pulls/{number}", self.owner, repo.name);
+        let endpoint = format!("repos/{}/pulls/{number}", repo.full_name);
         match self.get(endpoint, None).await {
             Ok(response) => {
                 let deserializer = &mut serde_json::Deserializer::from_str(&response);
@@ -53,13 +55,15 @@ impl Github {
         from: &String,
         to: &String,
     ) -> Result<Vec<PullRequest>, Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/pulls", self.owner, repo.name);
+        let endpoint = format!("repos/{}/pulls", repo.full_name);
+        // github ignores a `head` that isn't `owner:branch`
+        let head = format!("{}:{}", repo.owner.login, from);
         match self
             .get(
                 endpoint,
                 Some(&[
                     (&String::from("state"), &String::from("open")),
-                    (&String::from("head"), from),
+                    (&String::from("head"), &head),
                     (&String::from("base"), to),
                 ]),
             )
@@ -98,12 +102,26 @@ impl Github {
         to: &String,
         reference: &String,
     ) -> Result<PullRequest, Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/pulls", self.owner, repo.name);
-        let mut params = HashMap::<String, &String>::with_capacity(2);
         let title: String = format!("PR for: {}. {} into {}", reference, from, to);
-        params.insert(String::from("title"), &title);
+        self.open_pull(repo, from, to, &title, None).await
+    }
+
+    pub async fn open_pull(
+        &self,
+        repo: &Repo,
+        from: &String,
+        to: &String,
+        title: &String,
+        body: Option<&String>,
+    ) -> Result<PullRequest, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/pulls", repo.full_name);
+        let mut params = HashMap::<String, &String>::with_capacity(4);
+        params.insert(String::from("title"), title);
         params.insert(String::from("base"), to);
         params.insert(String::from("head"), from);
+        if let Some(body) = body {
+            params.insert(String::from("body"), body);
+        }
 
         match self.post(endpoint, Some(params)).await {
             Ok(response) => {
@@ -138,13 +156,21 @@ impl Github {
         &self,
         repo: &Repo,
         pull_request: &PullRequest,
+        merge_method: &MergeMethod,
     ) -> Result<PullRequestMergeStatus, Box<dyn GithubAPIError>> {
         let endpoint = format!(
-            "repos/{}/{}/pulls/{}/merge",
-            self.owner, repo.name, pull_request.number
+            "repos/{}/pulls/{}/merge",
+            repo.full_name, pull_request.number
         );
+        let merge_method = String::from(match merge_method {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        });
+        let mut params = HashMap::<String, &String>::with_capacity(1);
+        params.insert(String::from("merge_method"), &merge_method);
 
-        match self.put(endpoint, None).await {
+        match self.put(endpoint, Some(params)).await {
             Ok(response) => {
                 let ds = &mut serde_json::Deserializer::from_str(&response);
                 let result: Result<PullRequestMergeStatus, _> =
@@ -181,4 +207,140 @@ impl Github {
             },
         }
     }
+
+    pub async fn close_pull(
+        &self,
+        repo: &Repo,
+        pull_request: &PullRequest,
+    ) -> Result<PullRequest, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/pulls/{}", repo.full_name, pull_request.number);
+        let closed = String::from("closed");
+        let mut params = HashMap::<String, &String>::with_capacity(1);
+        params.insert(String::from("state"), &closed);
+
+        match self.patch(endpoint, Some(params)).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<PullRequest, _> = serde_path_to_error::deserialize(ds);
+
+                match result {
+                    Ok(pr) => Ok(pr),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!(
+                            "Error while closing pull request {}: {}",
+                            pull_request.number, e
+                        ),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("You are not allowed to close this pull request"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GithubAPIResponseError {
+                        message: String::from("Unprocessable entity"),
+                    }))
+                }
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn request_reviewers(
+        &self,
+        repo: &Repo,
+        pull_request: &PullRequest,
+        reviewers: &[String],
+    ) -> Result<PullRequest, Box<dyn GithubAPIError>> {
+        let endpoint = format!(
+            "repos/{}/pulls/{}/requested_reviewers",
+            repo.full_name, pull_request.number
+        );
+        let body = HashMap::from([("reviewers", reviewers)]);
+
+        match self.post_json(endpoint, &body).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<PullRequest, _> = serde_path_to_error::deserialize(ds);
+
+                match result {
+                    Ok(pr) => Ok(pr),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!(
+                            "Error while requesting reviewers for pull request {}: {}",
+                            pull_request.number, e
+                        ),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GithubAPIResponseError {
+                        message: String::from(
+                            "Reviewers can't be requested, they must be collaborators other than the author",
+                        ),
+                    }))
+                }
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("You are not allowed to request reviewers"),
+                })),
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn update_pull_branch(
+        &self,
+        repo: &Repo,
+        pull_request: &PullRequest,
+        expected_head_sha: &String,
+    ) -> Result<PullRequestUpdateBranchStatus, Box<dyn GithubAPIError>> {
+        let endpoint = format!(
+            "repos/{}/pulls/{}/update-branch",
+            repo.full_name, pull_request.number
+        );
+        let mut params = HashMap::<String, &String>::with_capacity(1);
+        params.insert(String::from("expected_head_sha"), expected_head_sha);
+
+        match self.put(endpoint, Some(params)).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<PullRequestUpdateBranchStatus, _> =
+                    serde_path_to_error::deserialize(ds);
+
+                match result {
+                    Ok(update_status) => Ok(update_status),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!(
+                            "Error while updating pull request {} branch: {}",
+                            pull_request.number, e
+                        ),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("You are not allowed to update this pull request"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GithubAPIResponseError {
+                        message: String::from(
+                            "Unprocessable entity, head branch may have moved since last fetch",
+                        ),
+                    }))
+                }
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
 }
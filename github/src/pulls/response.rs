@@ -15,24 +15,26 @@ pub enum GithubPullRequestState {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PullRequest {
-    pub id: u32,
+    pub id: u64,
     pub node_id: String,
     pub url: String,
     pub html_url: String,
-    pub diff_url: String,
-    pub issue_url: String,
-    pub patch_url: String,
-    pub commits_url: String,
-    pub review_comments_url: String,
-    pub review_comment_url: String,
-    pub comments_url: String,
-    pub statuses_url: String,
+    pub diff_url: Option<String>,
+    pub issue_url: Option<String>,
+    pub patch_url: Option<String>,
+    pub commits_url: Option<String>,
+    pub review_comments_url: Option<String>,
+    pub review_comment_url: Option<String>,
+    pub comments_url: Option<String>,
+    pub statuses_url: Option<String>,
     pub number: u64,
     pub state: GithubPullRequestState,
+    #[serde(default)]
     pub locked: bool,
     pub title: String,
     pub user: Option<User>,
     pub body: Option<String>,
+    #[serde(default)]
     pub labels: Vec<GithubLabel>,
     pub milestone: Option<GithubMilestone>,
     pub active_lock_reason: Option<String>,
@@ -47,9 +49,10 @@ pub struct PullRequest {
     pub requested_teams: Option<Vec<Team>>,
     pub head: GithubGitPointer,
     pub base: GithubGitPointer,
-    pub _links: Value, // TODO: links type ?
-    pub author_association: UserAssociation,
+    pub _links: Option<Value>, // TODO: links type ?
+    pub author_association: Option<UserAssociation>,
     pub auto_merge: Option<AutoMergeObject>,
+    #[serde(default)]
     pub draft: bool,
     pub merged: Option<bool>,
     pub mergeable: Option<bool>,
@@ -86,3 +89,9 @@ pub struct PullRequestMergeStatus {
     pub merged: bool,
     pub message: String,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PullRequestUpdateBranchStatus {
+    pub message: String,
+    pub url: String,
+}
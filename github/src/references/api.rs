@@ -11,10 +11,7 @@ impl Github {
         repo: &Repo,
         reference: &String,
     ) -> Result<Reference, Box<dyn GithubAPIError>> {
-        let endpoint = format!(
-            "repos/{}/{}/git/refs/heads/{reference}",
-            self.owner, repo.name
-        );
+        let endpoint = format!("repos/{}/git/refs/heads/{reference}", repo.full_name);
 
         match self.get(endpoint, None).await {
             Ok(response) => {
@@ -47,7 +44,7 @@ impl Github {
         branch_name: &String,
         from_ref: &Reference,
     ) -> Result<(), Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/git/refs", self.owner, repo.name);
+        let endpoint = format!("repos/{}/git/refs", repo.full_name);
 
         let mut params = HashMap::<String, &String>::with_capacity(2);
         let ref_ = format!("refs/heads/{branch_name}");
@@ -84,20 +81,11 @@ impl Github {
         repo: &Repo,
         reference: &String,
     ) -> Result<(), Box<dyn GithubAPIError>> {
-        let endpoint = format!("repos/{}/{}/git/refs/{}", self.owner, repo.name, reference);
+        let endpoint = format!("repos/{}/git/refs/{}", repo.full_name, reference);
 
         match self.delete(endpoint, None).await {
-            Ok(response) => {
-                let ds = &mut serde_json::Deserializer::from_str(&response);
-                let result: Result<DeleteReference, _> = serde_path_to_error::deserialize(ds);
-                match result {
-                    Ok(_) => Ok(()),
-                    Err(_) => Err(Box::new(GithubAPIResponseDeserializeError {
-                        parse_error: String::from(""),
-                        original_response: None,
-                    })),
-                }
-            }
+            // answered with `204 No Content`
+            Ok(_) => Ok(()),
             Err(status_code) => match status_code {
                 reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
                     Err(Box::new(GithubAPIResponseError {
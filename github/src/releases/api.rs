@@ -0,0 +1,72 @@
+use super::response::{NewRelease, Release};
+use crate::repos::response::Repo;
+use crate::Github;
+use crate::GithubAPIError;
+use crate::GithubAPIResponseDeserializeError;
+use crate::GithubAPIResponseError;
+
+impl Github {
+    /// The published release of `tag`, `None` when there is none.
+    pub async fn get_release_by_tag(
+        &self,
+        repo: &Repo,
+        tag: &str,
+    ) -> Result<Option<Release>, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/releases/tags/{tag}", repo.full_name);
+
+        match self.get(endpoint, None).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<Release, _> = serde_path_to_error::deserialize(ds);
+                match result {
+                    Ok(release) => Ok(Some(release)),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!("Unable to get release: {}", e),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Ok(None),
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn create_release(
+        &self,
+        repo: &Repo,
+        release: &NewRelease<'_>,
+    ) -> Result<Release, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/releases", repo.full_name);
+
+        match self.post_json(endpoint, release).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<Release, _> = serde_path_to_error::deserialize(ds);
+                match result {
+                    Ok(release) => Ok(release),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!("Unable to create release: {}", e),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("You are not allowed to create a release"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GithubAPIResponseError {
+                        message: String::from("Invalid release, the tag may already exist"),
+                    }))
+                }
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
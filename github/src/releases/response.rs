@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::users::response::User;
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Release {
+    pub id: u64,
+    pub node_id: String,
+    pub url: String,
+    pub html_url: String,
+    pub tag_name: String,
+    pub target_commitish: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    /// `None` for deleted accounts
+    pub author: Option<User>,
+    pub created_at: DateTime<Utc>,
+    /// `None` for drafts
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NewRelease<'a> {
+    pub tag_name: &'a str,
+    pub target_commitish: &'a str,
+    pub name: &'a str,
+    pub draft: bool,
+}
@@ -6,8 +6,13 @@ use crate::GithubAPIResponseError;
 use super::response::Repo;
 
 impl Github {
+    /// The repo `repo` of the owner, or of another one when given as
+    /// `owner/name`.
     pub async fn get_repo(&self, repo: &String) -> Result<Repo, Box<dyn GithubAPIError>> {
-        let endpoint: String = format!("repos/{}/{}", self.owner, repo);
+        let endpoint: String = match repo.contains('/') {
+            true => format!("repos/{}", repo),
+            false => format!("repos/{}/{}", self.owner, repo),
+        };
         match self.get(endpoint, None).await {
             Ok(response) => {
                 let ds = &mut serde_json::Deserializer::from_str(&response);
@@ -43,24 +48,105 @@ impl Github {
         if is_user.unwrap_or(false) {
             endpoint = format!("users/{owner}/repos");
         }
+        self.list_repo_pages(&endpoint, &[])
+            .await
+            .map_err(PageError::into_error)
+    }
 
-        match self.get(endpoint, None).await {
-            Ok(response) => {
-                let ds = &mut serde_json::Deserializer::from_str(&response);
-                let result: Result<Vec<Repo>, _> = serde_path_to_error::deserialize(ds);
-                match result {
-                    Ok(repos_list) => Ok(repos_list),
-                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
-                        parse_error: format!("Unable to parse response : {:?}", e),
-                        original_response: Some(response),
-                    })),
+    /// Every repo the authenticated user owns, private ones included, which
+    /// `users/{owner}/repos` leaves out.
+    pub async fn list_my_repos(&self) -> Result<Vec<Repo>, Box<dyn GithubAPIError>> {
+        self.list_repo_pages("user/repos", &[("affiliation", "owner")])
+            .await
+            .map_err(PageError::into_error)
+    }
+
+    /// Every repo of the owner, listed as an organization's, else as a user's:
+    /// all of them when the owner is the authenticated user, its public ones
+    /// otherwise.
+    pub async fn list_owner_repos(&self) -> Result<Vec<Repo>, Box<dyn GithubAPIError>> {
+        let endpoint = format!("orgs/{}/repos", self.owner);
+        match self.list_repo_pages(&endpoint, &[]).await {
+            Err(PageError::Status(reqwest::StatusCode::NOT_FOUND)) => {
+                if self.get_me().await?.login == self.owner {
+                    return self.list_my_repos().await;
                 }
+                let endpoint = format!("users/{}/repos", self.owner);
+                self.list_repo_pages(&endpoint, &[])
+                    .await
+                    .map_err(PageError::into_error)
             }
-            Err(status_code) => match status_code {
-                _ => Err(Box::new(GithubAPIResponseError {
-                    message: format!("Unhandled status code: {}", status_code),
-                })),
-            },
+            listed => listed.map_err(PageError::into_error),
+        }
+    }
+
+    /// Every page of a list of repos, `PER_PAGE` at a time, filtered by
+    /// `params`.
+    pub(crate) async fn list_repo_pages(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<Repo>, PageError> {
+        let per_page = PER_PAGE.to_string();
+        let params: Vec<(String, String)> = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let mut repos = Vec::new();
+        for page in 1.. {
+            let page = page.to_string();
+            let (per_page_key, page_key) = (String::from("per_page"), String::from("page"));
+            let query: Vec<(&String, &String)> = params
+                .iter()
+                .map(|(key, value)| (key, value))
+                .chain([(&per_page_key, &per_page), (&page_key, &page)])
+                .collect();
+            let response = self
+                .get(endpoint.to_string(), Some(&query[..]))
+                .await
+                .map_err(PageError::Status)?;
+            let ds = &mut serde_json::Deserializer::from_str(&response);
+            let result: Result<Vec<Repo>, _> = serde_path_to_error::deserialize(ds);
+            let listed = result.map_err(|e| PageError::Parse {
+                parse_error: format!("Unable to parse response : {:?}", e),
+                response: response.clone(),
+            })?;
+            let last = listed.len() < PER_PAGE;
+            repos.extend(listed);
+            if last {
+                break;
+            }
+        }
+        Ok(repos)
+    }
+}
+
+const PER_PAGE: usize = 100;
+
+pub(crate) enum PageError {
+    Status(reqwest::StatusCode),
+    Parse {
+        parse_error: String,
+        response: String,
+    },
+}
+
+impl PageError {
+    pub(crate) fn into_error(self) -> Box<dyn GithubAPIError> {
+        match self {
+            PageError::Status(reqwest::StatusCode::NOT_FOUND) => Box::new(GithubAPIResponseError {
+                message: String::from("Owner not found"),
+            }),
+            PageError::Status(status_code) => Box::new(GithubAPIResponseError {
+                message: format!("Unhandled status code: {}", status_code),
+            }),
+            PageError::Parse {
+                parse_error,
+                response,
+            } => Box::new(GithubAPIResponseDeserializeError {
+                parse_error,
+                original_response: Some(response),
+            }),
         }
     }
 }
@@ -1,9 +1,11 @@
 use crate::users::response::User;
 use serde_derive::{Deserialize, Serialize};
 
+/// Only the fields the client reads are required, URL templates and later
+/// additions are optional so new or trimmed payloads still deserialize.
 #[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct Repo {
-    pub id: u32,
+    pub id: u64,
     pub node_id: String,
     pub name: String,
     pub full_name: String,
@@ -13,27 +15,60 @@ pub struct Repo {
     pub description: Option<String>,
     pub fork: bool,
     pub url: String,
-    pub archive_url: String,
-    pub assignees_url: String,
-    pub blobs_url: String,
-    pub branches_url: String,
-    pub collaborators_url: String,
-    pub comments_url: String,
-    pub commits_url: String,
-    pub compare_url: String,
-    pub contents_url: String,
-    pub contributors_url: String,
-    pub deployments_url: String,
-    pub downloads_url: String,
-    pub events_url: String,
-    pub forks_url: String,
-    pub git_commits_url: String,
-    pub git_refs_url: String,
-    pub git_tags_url: String,
-    pub git_url: String,
-    pub issue_comment_url: String,
-    pub issue_events_url: String,
-    pub issues_url: String,
-    pub keys_url: String,
-    pub labels_url: String,
+    pub archive_url: Option<String>,
+    pub assignees_url: Option<String>,
+    pub blobs_url: Option<String>,
+    pub branches_url: Option<String>,
+    pub collaborators_url: Option<String>,
+    pub comments_url: Option<String>,
+    pub commits_url: Option<String>,
+    pub compare_url: Option<String>,
+    pub contents_url: Option<String>,
+    pub contributors_url: Option<String>,
+    pub deployments_url: Option<String>,
+    pub downloads_url: Option<String>,
+    pub events_url: Option<String>,
+    pub forks_url: Option<String>,
+    pub git_commits_url: Option<String>,
+    pub git_refs_url: Option<String>,
+    pub git_tags_url: Option<String>,
+    pub git_url: Option<String>,
+    pub issue_comment_url: Option<String>,
+    pub issue_events_url: Option<String>,
+    pub issues_url: Option<String>,
+    pub keys_url: Option<String>,
+    pub labels_url: Option<String>,
+
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub language: Option<String>,
+    pub visibility: Option<RepoVisibility>,
+    /// permissions of the authenticated user, only sent to authenticated requests
+    pub permissions: Option<RepoPermissions>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepoVisibility {
+    Public,
+    Private,
+    Internal,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct RepoPermissions {
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+    pub pull: bool,
 }
@@ -24,6 +24,8 @@ pub enum GithubDiffStatus {
     Copied,
     Changed,
     Unchanged,
+    #[serde(other)]
+    Unknown,
 }
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GithubDiffEntry {
@@ -33,11 +35,11 @@ pub struct GithubDiffEntry {
     pub additions: u64,
     pub deletions: u64,
     pub changes: u64,
-    pub blob_url: String,
-    pub raw_url: String,
-    pub contents_url: String,
-    pub patch: String,
-    pub previous_filename: String,
+    pub blob_url: Option<String>,
+    pub raw_url: Option<String>,
+    pub contents_url: Option<String>,
+    pub patch: Option<String>,
+    pub previous_filename: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -51,8 +53,8 @@ pub enum GithubMilestoneState {
 pub struct GithubMilestone {
     pub url: String,
     pub html_url: String,
-    pub labels_url: String,
-    pub id: u32,
+    pub labels_url: Option<String>,
+    pub id: u64,
     pub node_id: String,
     pub number: u32,
     pub state: GithubMilestoneState,
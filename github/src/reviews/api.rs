@@ -0,0 +1,38 @@
+use super::response::Review;
+use crate::repos::response::Repo;
+use crate::Github;
+use crate::GithubAPIError;
+use crate::GithubAPIResponseDeserializeError;
+use crate::GithubAPIResponseError;
+
+impl Github {
+    pub async fn list_reviews(
+        &self,
+        repo: &Repo,
+        number: u64,
+    ) -> Result<Vec<Review>, Box<dyn GithubAPIError>> {
+        let endpoint = format!("repos/{}/pulls/{number}/reviews", repo.full_name);
+
+        match self.get(endpoint, None).await {
+            Ok(response) => {
+                let ds = &mut serde_json::Deserializer::from_str(&response);
+                let result: Result<Vec<Review>, _> = serde_path_to_error::deserialize(ds);
+                match result {
+                    Ok(reviews) => Ok(reviews),
+                    Err(e) => Err(Box::new(GithubAPIResponseDeserializeError {
+                        parse_error: format!("Unable to get reviews: {}", e),
+                        original_response: Some(response),
+                    })),
+                }
+            }
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GithubAPIResponseError {
+                    message: String::from("Pull Request not found"),
+                })),
+                _ => Err(Box::new(GithubAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
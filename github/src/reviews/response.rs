@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::users::response::{User, UserAssociation};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Review {
+    pub id: u64,
+    pub node_id: String,
+    /// `None` for deleted accounts
+    pub user: Option<User>,
+    pub body: Option<String>,
+    pub state: ReviewState,
+    pub html_url: Option<String>,
+    pub commit_id: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub author_association: Option<UserAssociation>,
+}
@@ -0,0 +1,20 @@
+use crate::repos::api::PageError;
+use crate::repos::response::Repo;
+use crate::Github;
+use crate::GithubAPIError;
+use crate::GithubAPIResponseError;
+
+impl Github {
+    /// Repos the team `slug` of the owner, an organization, has access to.
+    pub async fn list_team_repos(&self, slug: &str) -> Result<Vec<Repo>, Box<dyn GithubAPIError>> {
+        let endpoint = format!("orgs/{}/teams/{}/repos", self.owner, slug);
+        match self.list_repo_pages(&endpoint, &[]).await {
+            Err(PageError::Status(reqwest::StatusCode::NOT_FOUND)) => {
+                Err(Box::new(GithubAPIResponseError {
+                    message: format!("Team {} not found", slug),
+                }))
+            }
+            listed => listed.map_err(PageError::into_error),
+        }
+    }
+}
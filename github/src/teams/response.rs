@@ -2,18 +2,18 @@ use serde_derive::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct Team {
-    pub id: u32,
+    pub id: u64,
     pub node_id: String,
     pub name: String,
     pub slug: String,
     pub ldap_dn: Option<String>,
     pub description: Option<String>,
     pub privacy: String,
-    pub permissions: TeamPermission,
+    pub permissions: Option<TeamPermission>,
     pub url: String,
     pub html_url: String,
-    pub members_url: String,
-    pub repositories_url: String,
+    pub members_url: Option<String>,
+    pub repositories_url: Option<String>,
     pub parent: Option<Box<Team>>,
 }
 
@@ -5,21 +5,21 @@ use serde_derive::{Deserialize, Serialize};
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct User {
     pub login: String,
-    pub id: u32,
+    pub id: u64,
     pub node_id: String,
-    pub avatar_url: String,
+    pub avatar_url: Option<String>,
     pub gravatar_id: Option<String>,
     pub url: String,
     pub html_url: String,
-    pub followers_url: String,
-    pub following_url: String,
+    pub followers_url: Option<String>,
+    pub following_url: Option<String>,
     pub gist_url: Option<String>,
-    pub starred_url: String,
-    pub subscriptions_url: String,
-    pub organizations_url: String,
-    pub repos_url: String,
-    pub events_url: String,
-    pub received_events_url: String,
+    pub starred_url: Option<String>,
+    pub subscriptions_url: Option<String>,
+    pub organizations_url: Option<String>,
+    pub repos_url: Option<String>,
+    pub events_url: Option<String>,
+    pub received_events_url: Option<String>,
 
     #[serde(rename = "type")]
     pub owner_type: String, // TODO: maybe enum
@@ -62,4 +62,6 @@ pub enum UserAssociation {
     Member,
     None,
     Owner,
+    #[serde(other)]
+    Unknown,
 }
@@ -0,0 +1,82 @@
+use github::pulls::response::PullRequest;
+use github::repos::response::{Repo, RepoVisibility};
+use github::users::response::UserAssociation;
+use serde_json::json;
+
+fn owner() -> serde_json::Value {
+    json!({
+        "login": "rednaks",
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "url": "https://api.github.com/users/rednaks",
+        "html_url": "https://github.com/rednaks",
+        "type": "Organization",
+        "site_admin": false
+    })
+}
+
+#[test]
+fn repos_without_url_templates_deserialize() {
+    let repo: Repo = serde_json::from_value(json!({
+        "id": 5_000_000_000u64,
+        "node_id": "R_kgDOABCDEF",
+        "name": "api",
+        "full_name": "rednaks/api",
+        "owner": owner(),
+        "private": true,
+        "html_url": "https://github.com/rednaks/api",
+        "description": null,
+        "fork": false,
+        "url": "https://api.github.com/repos/rednaks/api",
+        "default_branch": "develop",
+        "archived": true,
+        "topics": ["rust", "cli"],
+        "visibility": "internal",
+        "permissions": {"admin": false, "push": true, "pull": true}
+    }))
+    .unwrap();
+
+    assert_eq!(repo.default_branch.as_deref(), Some("develop"));
+    assert!(repo.archived);
+    assert!(!repo.disabled);
+    assert_eq!(repo.topics, vec!["rust", "cli"]);
+    assert_eq!(repo.visibility, Some(RepoVisibility::Internal));
+    let permissions = repo.permissions.unwrap();
+    assert!(permissions.push);
+    assert!(!permissions.maintain);
+}
+
+#[test]
+fn unknown_enum_values_are_tolerated() {
+    let pointer = |branch: &str| {
+        json!({
+            "label": format!("rednaks:{branch}"),
+            "ref": branch,
+            "sha": "1111111111111111111111111111111111111111",
+            "user": owner()
+        })
+    };
+    let pull: PullRequest = serde_json::from_value(json!({
+        "id": 3_000_000_000u64,
+        "node_id": "PR_kwDOABCDEF",
+        "url": "https://api.github.com/repos/rednaks/api/pulls/1",
+        "html_url": "https://github.com/rednaks/api/pull/1",
+        "number": 1,
+        "state": "open",
+        "title": "PR for: 3. main into prod",
+        "body": null,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+        "head": pointer("main"),
+        "base": pointer("prod"),
+        "author_association": "SPONSOR"
+    }))
+    .unwrap();
+
+    assert!(matches!(
+        pull.author_association,
+        Some(UserAssociation::Unknown)
+    ));
+    assert!(pull.labels.is_empty());
+    assert!(!pull.draft);
+}
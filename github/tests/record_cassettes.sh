@@ -0,0 +1,66 @@
+#!/bin/sh
+# Records real exchanges with api.github.com into github/tests/cassettes/github,
+# where `recorded_payloads_match_the_models` checks them against the models.
+#
+#   GITHUB_TOKEN=... github/tests/record_cassettes.sh <owner> <from> <to> <repo>...
+#
+# The token is only read from $GITHUB_TOKEN, the config written for the run
+# lives in a temporary directory. Only reads are made unless RECORD_PROMOTE=1,
+# which also opens and merges the pull requests of `from` into `to`: use it on
+# scratch repos. Set IS_USER=true when the owner is a user.
+#
+# Headers aren't recorded, the token and anything shaped like a GitHub token
+# are redacted. Review the cassettes before committing them all the same.
+set -eu
+
+if [ $# -lt 4 ] || [ -z "${GITHUB_TOKEN:-}" ]; then
+    sed -n '4,5p' "$0" | sed 's/^# *//' >&2
+    exit 64
+fi
+owner=$1
+from=$2
+to=$3
+shift 3
+first=$1
+
+root=$(cd "$(dirname "$0")/../.." && pwd)
+out=$root/github/tests/cassettes/github
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+repos=$(printf '"%s",' "$@")
+cat > "$tmp/config.json" <<EOF
+{
+  "org_name": "$owner",
+  "is_user": ${IS_USER:-false},
+  "token_source": {"from": "env", "var": "GITHUB_TOKEN"},
+  "repos": [${repos%,}]
+}
+EOF
+
+# one directory per command, each one writes `<owner>.json`
+record() {
+    name=$1
+    shift
+    cargo run --quiet --manifest-path "$root/Cargo.toml" -p cli -- \
+        --config "$tmp/config.json" --record "$tmp/$name" "$@" || true
+    if [ -f "$tmp/$name/$owner.json" ]; then
+        mkdir -p "$out"
+        mv "$tmp/$name/$owner.json" "$out/$name.json"
+        echo "recorded $out/$name.json"
+    fi
+}
+
+record status status --from "$from" --to "$to"
+record pulls pr list --from "$from" --to "$to"
+record select --select "name=$first" status --from "$from" --to "$to"
+if [ "${RECORD_PROMOTE:-0}" = 1 ]; then
+    record promotion promote --from "$from" --to "$to" --reference record \
+        --create-pulls --merge
+fi
+
+# the token must not have made it to a cassette
+if grep -rlF "$GITHUB_TOKEN" "$out"; then
+    echo "the token is in the cassettes above, they were not scrubbed" >&2
+    exit 1
+fi
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use super::response::{Branch, ProtectedBranch};
+use crate::{encode, parse, Gitlab, GitlabAPIError, GitlabAPIResponseError, PageError};
+
+impl Gitlab {
+    pub async fn list_branches(
+        &self,
+        project: &str,
+    ) -> Result<Vec<Branch>, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/repository/branches", self.project_id(project));
+
+        match self.get_pages(endpoint, &[], "branches").await {
+            Ok(branches) => Ok(branches),
+            Err(PageError::Parse(e)) => Err(e),
+            Err(PageError::Status(status_code)) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn create_branch(
+        &self,
+        project: &str,
+        branch: &str,
+        from: &str,
+    ) -> Result<Branch, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/repository/branches", self.project_id(project));
+        let mut params = HashMap::with_capacity(2);
+        params.insert("branch", branch);
+        params.insert("ref", from);
+
+        match self.post(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "created branch"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::BAD_REQUEST => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Branch already exists or invalid reference"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn delete_branch(
+        &self,
+        project: &str,
+        branch: &str,
+    ) -> Result<(), Box<dyn GitlabAPIError>> {
+        let endpoint = format!(
+            "projects/{}/repository/branches/{}",
+            self.project_id(project),
+            encode(branch)
+        );
+
+        match self.delete(endpoint).await {
+            Ok(_) => Ok(()),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Branch not found"),
+                })),
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Protected branches can't be deleted"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn list_protected_branches(
+        &self,
+        project: &str,
+    ) -> Result<Vec<ProtectedBranch>, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/protected_branches", self.project_id(project));
+
+        match self.get_pages(endpoint, &[], "protected branches").await {
+            Ok(branches) => Ok(branches),
+            Err(PageError::Parse(e)) => Err(e),
+            Err(PageError::Status(status_code)) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct BranchCommit {
+    pub id: String,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Branch {
+    pub name: String,
+    pub commit: BranchCommit,
+    pub protected: bool,
+    pub merged: Option<bool>,
+    pub default: Option<bool>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct ProtectedBranch {
+    pub id: u64,
+    /// branch name or wildcard, e.g. `release/*`
+    pub name: String,
+    pub allow_force_push: Option<bool>,
+}
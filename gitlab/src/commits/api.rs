@@ -0,0 +1,85 @@
+use super::response::{Commit, CommitStatus, Compare};
+use crate::{encode, parse, Gitlab, GitlabAPIError, GitlabAPIResponseError, PageError};
+
+impl Gitlab {
+    /// Commits and diffs on `to` since its merge base with `from`.
+    pub async fn compare(
+        &self,
+        project: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Compare, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/repository/compare", self.project_id(project));
+
+        match self
+            .get(
+                endpoint,
+                Some(&[("from", from), ("to", to), ("straight", "false")]),
+            )
+            .await
+        {
+            Ok(response) => parse(response, "comparison"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project or branch not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn list_commits(
+        &self,
+        project: &str,
+        ref_name: &str,
+        path: Option<&str>,
+        per_page: u8,
+    ) -> Result<Vec<Commit>, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/repository/commits", self.project_id(project));
+        let per_page = per_page.to_string();
+        let mut params = vec![("ref_name", ref_name), ("per_page", per_page.as_str())];
+        if let Some(path) = path {
+            params.push(("path", path));
+        }
+
+        match self.get(endpoint, Some(&params)).await {
+            Ok(response) => parse(response, "commits"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    /// Pipeline jobs and external statuses reported on `sha`.
+    pub async fn list_statuses(
+        &self,
+        project: &str,
+        sha: &str,
+    ) -> Result<Vec<CommitStatus>, Box<dyn GitlabAPIError>> {
+        let endpoint = format!(
+            "projects/{}/repository/commits/{}/statuses",
+            self.project_id(project),
+            encode(sha)
+        );
+
+        match self.get_pages(endpoint, &[], "commit statuses").await {
+            Ok(statuses) => Ok(statuses),
+            Err(PageError::Parse(e)) => Err(e),
+            Err(PageError::Status(status_code)) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Commit not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Commit {
+    pub id: String,
+    pub short_id: Option<String>,
+    pub title: Option<String>,
+    pub message: Option<String>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Diff {
+    pub old_path: String,
+    pub new_path: String,
+    pub new_file: bool,
+    pub renamed_file: bool,
+    pub deleted_file: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Compare {
+    /// head of the `to` side, `None` when there is nothing to compare
+    pub commit: Option<Commit>,
+    pub commits: Vec<Commit>,
+    pub diffs: Vec<Diff>,
+    pub compare_same_ref: Option<bool>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitStatusState {
+    Created,
+    WaitingForResource,
+    Preparing,
+    Pending,
+    Running,
+    Success,
+    Failed,
+    Canceled,
+    Skipped,
+    Manual,
+    Scheduled,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct CommitStatus {
+    pub id: u64,
+    pub name: String,
+    pub sha: String,
+    pub status: CommitStatusState,
+    pub allow_failure: Option<bool>,
+    pub target_url: Option<String>,
+}
@@ -0,0 +1,260 @@
+use async_trait::async_trait;
+use forge::{
+    Branch, BranchMerge, Check, CheckState, Commit, CompareStatus, Comparison, Forge, ForgeError,
+    MergeMethod, PullMerge, PullRequest, Repository,
+};
+
+use crate::commits::response::{self, CommitStatusState};
+use crate::merge_requests::response::{MergeRequest, MergeRequestState};
+use crate::{Gitlab, GitlabAPIError};
+
+impl From<Box<dyn GitlabAPIError>> for ForgeError {
+    fn from(e: Box<dyn GitlabAPIError>) -> Self {
+        ForgeError {
+            message: e.error_message(),
+            extra_info: e.extra_info(),
+        }
+    }
+}
+
+fn to_commit(commit: &response::Commit) -> Commit {
+    Commit {
+        sha: commit.id.clone(),
+        message: commit
+            .message
+            .clone()
+            .or_else(|| commit.title.clone())
+            .unwrap_or_default(),
+        author: commit.author_name.clone(),
+    }
+}
+
+fn to_pull_request(mr: &MergeRequest) -> PullRequest {
+    PullRequest {
+        number: mr.iid,
+        title: mr.title.clone(),
+        url: mr.web_url.clone(),
+        head: mr.source_branch.clone(),
+        base: mr.target_branch.clone(),
+        head_sha: mr.sha.clone().unwrap_or_default(),
+        mergeable: match (mr.has_conflicts, mr.merge_status.as_deref()) {
+            (Some(true), _) | (_, Some("cannot_be_merged")) => Some(false),
+            (_, Some("can_be_merged")) => Some(true),
+            _ => None,
+        },
+        // the promotion flow speaks github's vocabulary
+        mergeable_state: mr
+            .detailed_merge_status
+            .as_deref()
+            .map(|status| match status {
+                "mergeable" => String::from("clean"),
+                "need_rebase" => String::from("behind"),
+                "conflict" => String::from("dirty"),
+                other => other.to_string(),
+            }),
+    }
+}
+
+#[async_trait]
+impl Forge for Gitlab {
+    async fn get_repo(&self, name: &str) -> Result<Repository, ForgeError> {
+        let project = self.get_project(name).await?;
+        Ok(Repository {
+            name: project.path,
+            full_name: project.path_with_namespace,
+            web_url: project.web_url,
+            clone_url: project.http_url_to_repo,
+            default_branch: project.default_branch,
+            archived: project.archived.unwrap_or(false),
+        })
+    }
+
+    async fn list_branches(&self, repo: &Repository) -> Result<Vec<Branch>, ForgeError> {
+        let branches = Gitlab::list_branches(self, &repo.name).await?;
+        Ok(branches
+            .iter()
+            .map(|branch| Branch {
+                name: branch.name.clone(),
+                sha: branch.commit.id.clone(),
+                protected: branch.protected,
+            })
+            .collect())
+    }
+
+    async fn compare_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+    ) -> Result<Comparison, ForgeError> {
+        // gitlab only lists one side of the comparison at a time
+        let ahead = self.compare(&repo.name, head, base).await?;
+        let behind = self.compare(&repo.name, base, head).await?;
+        let ahead_by = ahead.commits.len() as u64;
+        let behind_by = behind.commits.len() as u64;
+
+        Ok(Comparison {
+            status: match (ahead_by, behind_by) {
+                (0, 0) => CompareStatus::Identical,
+                (_, 0) => CompareStatus::Ahead,
+                (0, _) => CompareStatus::Behind,
+                _ => CompareStatus::Diverged,
+            },
+            ahead_by,
+            behind_by,
+            commits: ahead.commits.iter().map(to_commit).collect(),
+            files: ahead
+                .diffs
+                .iter()
+                .map(|diff| diff.new_path.clone())
+                .collect(),
+        })
+    }
+
+    async fn create_branch(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        from: &str,
+    ) -> Result<(), ForgeError> {
+        Gitlab::create_branch(self, &repo.name, branch, from).await?;
+        Ok(())
+    }
+
+    async fn delete_branch(&self, repo: &Repository, branch: &str) -> Result<(), ForgeError> {
+        Ok(Gitlab::delete_branch(self, &repo.name, branch).await?)
+    }
+
+    async fn merge_branches(
+        &self,
+        _repo: &Repository,
+        _base: &str,
+        _head: &str,
+        _commit_message: &str,
+    ) -> Result<BranchMerge, ForgeError> {
+        // there is no endpoint merging branches outside of a merge request
+        Err(ForgeError::unsupported("direct merges"))
+    }
+
+    async fn find_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<PullRequest>, ForgeError> {
+        let merge_requests = self.list_merge_requests(&repo.name, from, to).await?;
+        match merge_requests.first() {
+            // listed merge requests don't carry their detailed merge status
+            Some(mr) => {
+                let mr = self.get_merge_request(&repo.name, mr.iid).await?;
+                Ok(Some(to_pull_request(&mr)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_pull(&self, repo: &Repository, number: u64) -> Result<PullRequest, ForgeError> {
+        Ok(to_pull_request(
+            &self.get_merge_request(&repo.name, number).await?,
+        ))
+    }
+
+    async fn create_pull(
+        &self,
+        repo: &Repository,
+        from: &str,
+        to: &str,
+        title: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest, ForgeError> {
+        let mr = self
+            .create_merge_request(&repo.name, from, to, title, body)
+            .await?;
+        Ok(to_pull_request(&mr))
+    }
+
+    async fn merge_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+        method: MergeMethod,
+    ) -> Result<PullMerge, ForgeError> {
+        // fast-forward merges are a project setting, not a merge parameter
+        if method == MergeMethod::Rebase {
+            return Err(ForgeError::unsupported("rebase merging"));
+        }
+        let sha = Some(pull_request.head_sha.as_str()).filter(|sha| !sha.is_empty());
+        let mr = self
+            .merge_merge_request(
+                &repo.name,
+                pull_request.number,
+                sha,
+                method == MergeMethod::Squash,
+            )
+            .await?;
+        let merged = mr.state == MergeRequestState::Merged;
+        Ok(PullMerge {
+            merged,
+            sha: mr.merge_commit_sha.or(mr.squash_commit_sha),
+            message: String::from(if merged {
+                "Merge request merged"
+            } else {
+                "Merge request not merged"
+            }),
+        })
+    }
+
+    async fn close_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        self.close_merge_request(&repo.name, pull_request.number)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_pull(
+        &self,
+        repo: &Repository,
+        pull_request: &PullRequest,
+    ) -> Result<(), ForgeError> {
+        self.rebase_merge_request(&repo.name, pull_request.number)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_checks(&self, repo: &Repository, sha: &str) -> Result<Vec<Check>, ForgeError> {
+        let statuses = self.list_statuses(&repo.name, sha).await?;
+        Ok(statuses
+            .iter()
+            .map(|status| Check {
+                name: status.name.clone(),
+                state: match (&status.status, status.allow_failure.unwrap_or(false)) {
+                    (CommitStatusState::Success, _) | (CommitStatusState::Skipped, _) => {
+                        CheckState::Success
+                    }
+                    (CommitStatusState::Failed, true)
+                    | (CommitStatusState::Canceled, true)
+                    | (CommitStatusState::Manual, true) => CheckState::Success,
+                    (CommitStatusState::Failed, false) | (CommitStatusState::Canceled, false) => {
+                        CheckState::Failure
+                    }
+                    _ => CheckState::Pending,
+                },
+            })
+            .collect())
+    }
+
+    async fn last_author(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        path: &str,
+    ) -> Result<Option<String>, ForgeError> {
+        let commits = self.list_commits(&repo.name, branch, Some(path), 1).await?;
+        Ok(commits
+            .first()
+            .and_then(|commit| commit.author_name.clone()))
+    }
+}
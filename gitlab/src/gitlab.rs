@@ -0,0 +1,8 @@
+pub struct Gitlab {
+    pub client: reqwest::Client,
+    /// instance root, e.g. `https://gitlab.com`
+    pub url: String,
+    /// group (or user namespace) the managed projects live in
+    pub group: String,
+    pub token: String,
+}
@@ -0,0 +1,182 @@
+pub mod branches;
+pub mod commits;
+mod forge;
+mod gitlab;
+pub mod merge_requests;
+pub mod projects;
+
+pub use gitlab::Gitlab;
+use log::debug;
+
+use std::collections::HashMap;
+
+use reqwest::{header, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+pub trait GitlabAPIError {
+    fn error_message(&self) -> String;
+    fn extra_info(&self) -> Option<String>;
+}
+
+pub struct GitlabAPIResponseDeserializeError {
+    pub parse_error: String,
+    pub original_response: Option<String>,
+}
+
+pub struct GitlabAPIResponseError {
+    pub message: String,
+}
+
+impl GitlabAPIError for GitlabAPIResponseError {
+    fn error_message(&self) -> String {
+        self.message.clone()
+    }
+
+    fn extra_info(&self) -> Option<String> {
+        None
+    }
+}
+
+impl GitlabAPIError for GitlabAPIResponseDeserializeError {
+    fn error_message(&self) -> String {
+        self.parse_error.clone()
+    }
+
+    fn extra_info(&self) -> Option<String> {
+        self.original_response.clone()
+    }
+}
+
+/// Percent-encodes a path segment: project paths (`group/project`) and branch
+/// names are used as ids in the v4 API and may contain slashes.
+fn encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Items per page of the lists walked with [`Gitlab::get_pages`].
+const PER_PAGE: usize = 100;
+
+/// Why a list could not be read in full.
+enum PageError {
+    Status(reqwest::StatusCode),
+    Parse(Box<dyn GitlabAPIError>),
+}
+
+fn parse<T: DeserializeOwned>(response: String, what: &str) -> Result<T, Box<dyn GitlabAPIError>> {
+    let ds = &mut serde_json::Deserializer::from_str(&response);
+    let result: Result<T, _> = serde_path_to_error::deserialize(ds);
+    match result {
+        Ok(value) => Ok(value),
+        Err(e) => Err(Box::new(GitlabAPIResponseDeserializeError {
+            parse_error: format!("Unable to get {}: {}", what, e),
+            original_response: Some(response),
+        })),
+    }
+}
+
+impl Gitlab {
+    pub fn new(url: String, token: String, group: String) -> Gitlab {
+        Gitlab {
+            client: reqwest::Client::new(),
+            url,
+            group,
+            token,
+        }
+    }
+
+    /// Id of `project` in the configured group, as expected in endpoint paths.
+    fn project_id(&self, project: &str) -> String {
+        encode(&format!("{}/{}", self.group, project))
+    }
+
+    fn endpoint_url(&self, endpoint: String) -> String {
+        format!("{}/api/v4/{}", self.url.trim_end_matches('/'), endpoint)
+    }
+
+    fn add_headers(&self, req: RequestBuilder) -> RequestBuilder {
+        req.header("PRIVATE-TOKEN", &self.token)
+            .header(header::USER_AGENT, "MultiGitRs")
+            .header(header::ACCEPT, "application/json")
+    }
+
+    async fn send(&self, req: RequestBuilder) -> Result<String, reqwest::StatusCode> {
+        let r: Response = req.send().await.unwrap();
+
+        if let Err(e) = r.error_for_status_ref() {
+            let response = r.text().await.unwrap();
+            debug!("error response: {:?}", response);
+            Err(e.status().unwrap())
+        } else {
+            Ok(r.text().await.unwrap())
+        }
+    }
+
+    async fn get(
+        &self,
+        endpoint: String,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.get(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).query(&params)).await
+    }
+
+    /// Every page of a list, `PER_PAGE` items at a time.
+    async fn get_pages<T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        params: &[(&str, &str)],
+        what: &str,
+    ) -> Result<Vec<T>, PageError> {
+        let per_page = PER_PAGE.to_string();
+        let mut items = Vec::new();
+        for page in 1.. {
+            let page = page.to_string();
+            let mut query = params.to_vec();
+            query.push(("per_page", &per_page));
+            query.push(("page", &page));
+            let response = self
+                .get(endpoint.clone(), Some(&query))
+                .await
+                .map_err(PageError::Status)?;
+            let listed: Vec<T> = parse(response, what).map_err(PageError::Parse)?;
+            let last = listed.len() < PER_PAGE;
+            items.extend(listed);
+            if last {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    async fn post(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<&str, &str>>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.post(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).json(&params)).await
+    }
+
+    async fn put(
+        &self,
+        endpoint: String,
+        params: Option<HashMap<&str, &str>>,
+    ) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.put(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req).json(&params)).await
+    }
+
+    async fn delete(&self, endpoint: String) -> Result<String, reqwest::StatusCode> {
+        let req = self.client.delete(self.endpoint_url(endpoint));
+        self.send(self.add_headers(req)).await
+    }
+}
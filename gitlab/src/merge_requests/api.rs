@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use super::response::{MergeRequest, MergeRequestRebase};
+use crate::{parse, Gitlab, GitlabAPIError, GitlabAPIResponseError};
+
+impl Gitlab {
+    pub async fn list_merge_requests(
+        &self,
+        project: &str,
+        source_branch: &str,
+        target_branch: &str,
+    ) -> Result<Vec<MergeRequest>, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/merge_requests", self.project_id(project));
+
+        match self
+            .get(
+                endpoint,
+                Some(&[
+                    ("state", "opened"),
+                    ("source_branch", source_branch),
+                    ("target_branch", target_branch),
+                ]),
+            )
+            .await
+        {
+            Ok(response) => parse(response, "list of merge requests"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn get_merge_request(
+        &self,
+        project: &str,
+        iid: u64,
+    ) -> Result<MergeRequest, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/merge_requests/{iid}", self.project_id(project));
+
+        match self.get(endpoint, None).await {
+            Ok(response) => parse(response, "merge request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Merge request not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn create_merge_request(
+        &self,
+        project: &str,
+        source_branch: &str,
+        target_branch: &str,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<MergeRequest, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/merge_requests", self.project_id(project));
+        let mut params = HashMap::with_capacity(4);
+        params.insert("source_branch", source_branch);
+        params.insert("target_branch", target_branch);
+        params.insert("title", title);
+        if let Some(description) = description {
+            params.insert("description", description);
+        }
+
+        match self.post(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "created merge request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("A merge request already exists for these branches"),
+                })),
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Box::new(GitlabAPIResponseError {
+                        message: String::from("Validation failed"),
+                    }))
+                }
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    /// Accepts the merge request, squashing its commits with `squash`, refusing
+    /// if its head moved past `sha`.
+    pub async fn merge_merge_request(
+        &self,
+        project: &str,
+        iid: u64,
+        sha: Option<&str>,
+        squash: bool,
+    ) -> Result<MergeRequest, Box<dyn GitlabAPIError>> {
+        let endpoint = format!(
+            "projects/{}/merge_requests/{iid}/merge",
+            self.project_id(project)
+        );
+        let mut params = HashMap::with_capacity(2);
+        if let Some(sha) = sha {
+            params.insert("sha", sha);
+        }
+        if squash {
+            params.insert("squash", "true");
+        }
+
+        match self.put(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "merged merge request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::METHOD_NOT_ALLOWED => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from(
+                        "Merge request is not mergeable (draft, closed or blocked)",
+                    ),
+                })),
+                reqwest::StatusCode::NOT_ACCEPTABLE => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Merge request has conflicts"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Head branch was modified, review and try again"),
+                })),
+                reqwest::StatusCode::UNAUTHORIZED => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("You are not allowed to merge this merge request"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    pub async fn close_merge_request(
+        &self,
+        project: &str,
+        iid: u64,
+    ) -> Result<MergeRequest, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}/merge_requests/{iid}", self.project_id(project));
+        let params = HashMap::from([("state_event", "close")]);
+
+        match self.put(endpoint, Some(params)).await {
+            Ok(response) => parse(response, "closed merge request"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("You are not allowed to close this merge request"),
+                })),
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Merge request not found"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+
+    /// Rebases the source branch onto the target branch, asynchronously.
+    pub async fn rebase_merge_request(
+        &self,
+        project: &str,
+        iid: u64,
+    ) -> Result<MergeRequestRebase, Box<dyn GitlabAPIError>> {
+        let endpoint = format!(
+            "projects/{}/merge_requests/{iid}/rebase",
+            self.project_id(project)
+        );
+
+        match self.put(endpoint, None).await {
+            Ok(response) => parse(response, "rebase status"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::FORBIDDEN => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("You are not allowed to push to the source branch"),
+                })),
+                reqwest::StatusCode::CONFLICT => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("A rebase is already in progress"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeRequestState {
+    Opened,
+    Closed,
+    Locked,
+    Merged,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct MergeRequest {
+    pub id: u64,
+    /// number of the merge request within its project
+    pub iid: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: MergeRequestState,
+    pub web_url: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub sha: Option<String>,
+    /// `can_be_merged`, `cannot_be_merged`, `unchecked`, `checking`...
+    pub merge_status: Option<String>,
+    /// `mergeable`, `need_rebase`, `conflict`, `ci_still_running`...
+    pub detailed_merge_status: Option<String>,
+    pub has_conflicts: Option<bool>,
+    pub merge_commit_sha: Option<String>,
+    pub squash_commit_sha: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct MergeRequestRebase {
+    pub rebase_in_progress: bool,
+}
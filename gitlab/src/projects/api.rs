@@ -0,0 +1,23 @@
+use super::response::Project;
+use crate::{parse, Gitlab, GitlabAPIError, GitlabAPIResponseError};
+
+impl Gitlab {
+    pub async fn get_project(&self, name: &str) -> Result<Project, Box<dyn GitlabAPIError>> {
+        let endpoint = format!("projects/{}", self.project_id(name));
+
+        match self.get(endpoint, None).await {
+            Ok(response) => parse(response, "project"),
+            Err(status_code) => match status_code {
+                reqwest::StatusCode::NOT_FOUND => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Project not found"),
+                })),
+                reqwest::StatusCode::UNAUTHORIZED => Err(Box::new(GitlabAPIResponseError {
+                    message: String::from("Unauthorized, check the gitlab token"),
+                })),
+                _ => Err(Box::new(GitlabAPIResponseError {
+                    message: format!("Unhandled: {}", status_code),
+                })),
+            },
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Project {
+    pub id: u64,
+    pub name: String,
+    pub path: String,
+    pub path_with_namespace: String,
+    pub web_url: String,
+    pub http_url_to_repo: String,
+    pub default_branch: Option<String>,
+    pub archived: Option<bool>,
+}
@@ -0,0 +1,470 @@
+use forge::{CheckState, CompareStatus, Forge, ForgeError, MergeMethod, Repository};
+use gitlab::Gitlab;
+use serde_json::{json, Value};
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PROJECT: &str = "/api/v4/projects/platform%2Fapi";
+
+fn gitlab(server: &MockServer) -> Gitlab {
+    Gitlab::new(
+        server.uri(),
+        String::from("glpat-test"),
+        String::from("platform"),
+    )
+}
+
+fn repository() -> Repository {
+    Repository {
+        name: String::from("api"),
+        full_name: String::from("platform/api"),
+        web_url: String::from("https://gitlab.example.com/platform/api"),
+        clone_url: String::from("https://gitlab.example.com/platform/api.git"),
+        default_branch: Some(String::from("main")),
+        archived: false,
+    }
+}
+
+fn commit(id: &str, author: &str) -> Value {
+    json!({
+        "id": id,
+        "short_id": &id[..8],
+        "title": format!("commit {}", id),
+        "message": format!("commit {}\n", id),
+        "author_name": author,
+        "author_email": "dev@example.com",
+        "web_url": format!("https://gitlab.example.com/platform/api/-/commit/{}", id)
+    })
+}
+
+fn compare(commits: Vec<Value>, paths: &[&str]) -> Value {
+    let diffs: Vec<Value> = paths
+        .iter()
+        .map(|path| {
+            json!({
+                "old_path": path,
+                "new_path": path,
+                "a_mode": "100644",
+                "b_mode": "100644",
+                "diff": "@@ -1 +1 @@\n-a\n+b\n",
+                "new_file": false,
+                "renamed_file": false,
+                "deleted_file": false
+            })
+        })
+        .collect();
+    json!({
+        "commit": commits.last(),
+        "commits": commits,
+        "diffs": diffs,
+        "compare_timeout": false,
+        "compare_same_ref": false,
+        "web_url": "https://gitlab.example.com/platform/api/-/compare/a...b"
+    })
+}
+
+fn merge_request(state: &str, detailed_merge_status: &str) -> Value {
+    json!({
+        "id": 9001,
+        "iid": 12,
+        "project_id": 42,
+        "title": "PR for: 3. main into prod",
+        "description": null,
+        "state": state,
+        "web_url": "https://gitlab.example.com/platform/api/-/merge_requests/12",
+        "source_branch": "main",
+        "target_branch": "prod",
+        "sha": "1111111111111111111111111111111111111111",
+        "merge_status": if detailed_merge_status == "conflict" { "cannot_be_merged" } else { "can_be_merged" },
+        "detailed_merge_status": detailed_merge_status,
+        "has_conflicts": detailed_merge_status == "conflict",
+        "merge_commit_sha": if state == "merged" { json!("2222222222222222222222222222222222222222") } else { Value::Null },
+        "squash_commit_sha": null
+    })
+}
+
+#[tokio::test]
+async fn get_repo_reads_the_project_in_the_group() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(PROJECT))
+        .and(header("PRIVATE-TOKEN", "glpat-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "name": "API",
+            "path": "api",
+            "path_with_namespace": "platform/api",
+            "web_url": "https://gitlab.example.com/platform/api",
+            "http_url_to_repo": "https://gitlab.example.com/platform/api.git",
+            "ssh_url_to_repo": "git@gitlab.example.com:platform/api.git",
+            "default_branch": "main",
+            "archived": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let repo = gitlab(&server).get_repo("api").await.unwrap();
+
+    assert_eq!(repo.name, "api");
+    assert_eq!(repo.full_name, "platform/api");
+    assert_eq!(
+        repo.clone_url,
+        "https://gitlab.example.com/platform/api.git"
+    );
+    assert_eq!(repo.default_branch.as_deref(), Some("main"));
+    assert!(!repo.archived);
+}
+
+#[tokio::test]
+async fn get_repo_reports_missing_projects() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(PROJECT))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(json!({"message": "404 Project Not Found"})),
+        )
+        .mount(&server)
+        .await;
+
+    let error = gitlab(&server).get_repo("api").await.unwrap_err();
+
+    assert_eq!(error.error_message(), "Project not found");
+}
+
+#[tokio::test]
+async fn list_branches_keeps_protection() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/repository/branches", PROJECT)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "name": "main",
+                "commit": commit("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "Ada"),
+                "merged": false,
+                "protected": false,
+                "default": true
+            },
+            {
+                "name": "prod",
+                "commit": commit("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb", "Ada"),
+                "merged": false,
+                "protected": true,
+                "default": false
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let branches = Forge::list_branches(&gitlab(&server), &repository())
+        .await
+        .unwrap();
+
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[0].sha, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    assert!(!branches[0].protected);
+    assert!(branches[1].protected);
+}
+
+#[tokio::test]
+async fn list_branches_reads_every_page() {
+    let server = MockServer::start().await;
+    let branch = |index: usize| {
+        json!({
+            "name": format!("feature/{}", index),
+            "commit": commit("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "Ada"),
+            "merged": false,
+            "protected": false,
+            "default": false
+        })
+    };
+    let first: Vec<Value> = (0..100).map(branch).collect();
+    Mock::given(method("GET"))
+        .and(path(format!("{}/repository/branches", PROJECT)))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!(first)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/repository/branches", PROJECT)))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([branch(100)])))
+        .mount(&server)
+        .await;
+
+    let branches = Forge::list_branches(&gitlab(&server), &repository())
+        .await
+        .unwrap();
+
+    assert_eq!(branches.len(), 101);
+    assert_eq!(branches[100].name, "feature/100");
+}
+
+#[tokio::test]
+async fn compare_branches_combines_both_sides() {
+    let server = MockServer::start().await;
+    // commits on prod missing from main
+    Mock::given(method("GET"))
+        .and(path(format!("{}/repository/compare", PROJECT)))
+        .and(query_param("from", "main"))
+        .and(query_param("to", "prod"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(compare(
+            vec![commit("cccccccccccccccccccccccccccccccccccccccc", "Grace")],
+            &["hotfix.txt"],
+        )))
+        .mount(&server)
+        .await;
+    // commits on main missing from prod
+    Mock::given(method("GET"))
+        .and(path(format!("{}/repository/compare", PROJECT)))
+        .and(query_param("from", "prod"))
+        .and(query_param("to", "main"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(compare(
+            vec![
+                commit("dddddddddddddddddddddddddddddddddddddddd", "Ada"),
+                commit("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee", "Ada"),
+            ],
+            &["feature.txt"],
+        )))
+        .mount(&server)
+        .await;
+
+    let comparison = gitlab(&server)
+        .compare_branches(&repository(), "prod", "main")
+        .await
+        .unwrap();
+
+    assert_eq!(comparison.status, CompareStatus::Diverged);
+    assert_eq!(comparison.ahead_by, 1);
+    assert_eq!(comparison.behind_by, 2);
+    assert_eq!(comparison.commits[0].author.as_deref(), Some("Grace"));
+    assert_eq!(comparison.files, vec![String::from("hotfix.txt")]);
+}
+
+#[tokio::test]
+async fn create_and_delete_branches() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/repository/branches", PROJECT)))
+        .and(body_json(json!({"branch": "release/3", "ref": "main"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "release/3",
+            "commit": commit("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", "Ada"),
+            "merged": false,
+            "protected": false,
+            "default": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("{}/repository/branches/release%2F3", PROJECT)))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let gitlab = gitlab(&server);
+    Forge::create_branch(&gitlab, &repository(), "release/3", "main")
+        .await
+        .unwrap();
+    Forge::delete_branch(&gitlab, &repository(), "release/3")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn protected_branches_are_listed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/protected_branches", PROJECT)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": 1,
+                "name": "prod",
+                "push_access_levels": [],
+                "merge_access_levels": [],
+                "allow_force_push": false
+            },
+            {
+                "id": 2,
+                "name": "release/*",
+                "push_access_levels": [],
+                "merge_access_levels": [],
+                "allow_force_push": false
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let protected = gitlab(&server)
+        .list_protected_branches("api")
+        .await
+        .map_err(ForgeError::from)
+        .unwrap();
+
+    let names: Vec<&str> = protected
+        .iter()
+        .map(|branch| branch.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["prod", "release/*"]);
+}
+
+#[tokio::test]
+async fn merge_requests_are_opened_found_and_merged() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/merge_requests", PROJECT)))
+        .and(query_param("state", "opened"))
+        .and(query_param("source_branch", "main"))
+        .and(query_param("target_branch", "prod"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{}/merge_requests", PROJECT)))
+        .and(body_json(json!({
+            "source_branch": "main",
+            "target_branch": "prod",
+            "title": "PR for: 3. main into prod"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(merge_request("opened", "checking")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/merge_requests/12", PROJECT)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(merge_request("opened", "mergeable")),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(format!("{}/merge_requests/12/merge", PROJECT)))
+        .and(body_json(json!({
+            "sha": "1111111111111111111111111111111111111111",
+            "squash": "true"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(merge_request("merged", "not_open")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let gitlab = gitlab(&server);
+    let repo = repository();
+
+    assert!(gitlab
+        .find_pull(&repo, "main", "prod")
+        .await
+        .unwrap()
+        .is_none());
+
+    let created = gitlab
+        .create_pull(&repo, "main", "prod", "PR for: 3. main into prod", None)
+        .await
+        .unwrap();
+    assert_eq!(created.number, 12);
+    assert_eq!(created.mergeable, Some(true));
+
+    let pr = gitlab.get_pull(&repo, created.number).await.unwrap();
+    assert_eq!(pr.mergeable_state.as_deref(), Some("clean"));
+
+    let merge = gitlab
+        .merge_pull(&repo, &pr, MergeMethod::Squash)
+        .await
+        .unwrap();
+    assert!(merge.merged);
+    assert_eq!(
+        merge.sha.as_deref(),
+        Some("2222222222222222222222222222222222222222")
+    );
+}
+
+#[tokio::test]
+async fn conflicting_merge_requests_are_not_mergeable() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/merge_requests", PROJECT)))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([merge_request("opened", "conflict")])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{}/merge_requests/12", PROJECT)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(merge_request("opened", "conflict")))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(format!("{}/merge_requests/12/merge", PROJECT)))
+        .respond_with(
+            ResponseTemplate::new(406).set_body_json(json!({"message": "Branch cannot be merged"})),
+        )
+        .mount(&server)
+        .await;
+
+    let gitlab = gitlab(&server);
+    let repo = repository();
+
+    let pr = gitlab
+        .find_pull(&repo, "main", "prod")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pr.mergeable, Some(false));
+    assert_eq!(pr.mergeable_state.as_deref(), Some("dirty"));
+
+    let error = gitlab
+        .merge_pull(&repo, &pr, MergeMethod::Merge)
+        .await
+        .unwrap_err();
+    assert_eq!(error.error_message(), "Merge request has conflicts");
+}
+
+#[tokio::test]
+async fn pipeline_statuses_map_to_checks() {
+    let server = MockServer::start().await;
+    let sha = "1111111111111111111111111111111111111111";
+    Mock::given(method("GET"))
+        .and(path(format!(
+            "{}/repository/commits/{}/statuses",
+            PROJECT, sha
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 1, "name": "test", "sha": sha, "status": "success", "allow_failure": false},
+            {"id": 2, "name": "lint", "sha": sha, "status": "failed", "allow_failure": true},
+            {"id": 3, "name": "build", "sha": sha, "status": "running", "allow_failure": false},
+            {"id": 4, "name": "audit", "sha": sha, "status": "failed", "allow_failure": false}
+        ])))
+        .mount(&server)
+        .await;
+
+    let checks = gitlab(&server)
+        .list_checks(&repository(), sha)
+        .await
+        .unwrap();
+
+    let states: Vec<CheckState> = checks.into_iter().map(|check| check.state).collect();
+    assert_eq!(
+        states,
+        vec![
+            CheckState::Success,
+            CheckState::Success,
+            CheckState::Pending,
+            CheckState::Failure
+        ]
+    );
+}
+
+#[tokio::test]
+async fn direct_merges_are_unsupported() {
+    let server = MockServer::start().await;
+
+    let error = gitlab(&server)
+        .merge_branches(&repository(), "prod", "main", "merge main into prod")
+        .await
+        .unwrap_err();
+
+    assert!(error.error_message().contains("not supported"));
+}
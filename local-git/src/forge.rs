@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use forge::{
+    Branch, BranchMerge, Commit, CompareStatus, Comparison, Forge, ForgeError, Repository,
+};
+use git2::{BranchType, Oid, Repository as GitRepository, Signature, Sort};
+
+use crate::merge::branch_commit;
+use crate::{LocalGit, LocalGitError};
+
+impl From<LocalGitError> for ForgeError {
+    fn from(e: LocalGitError) -> Self {
+        ForgeError::new(e.message)
+    }
+}
+
+/// commits reachable from `include` but not from `exclude`, newest first
+fn commits_between(
+    repo: &GitRepository,
+    include: Oid,
+    exclude: Oid,
+) -> Result<Vec<Oid>, LocalGitError> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL)?;
+    walk.push(include)?;
+    walk.hide(exclude)?;
+    let mut oids = vec![];
+    for oid in walk {
+        oids.push(oid?);
+    }
+    Ok(oids)
+}
+
+fn list_branches(repo: &GitRepository) -> Result<Vec<Branch>, LocalGitError> {
+    let mut branches = vec![];
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        let name = branch.name()?.unwrap_or_default().to_string();
+        let sha = branch
+            .get()
+            .target()
+            .map(|oid| oid.to_string())
+            .unwrap_or_default();
+        branches.push(Branch {
+            name,
+            sha,
+            protected: false,
+        });
+    }
+    Ok(branches)
+}
+
+/// Branch `HEAD` points to, bare repositories have no checkout to follow.
+fn default_branch(repo: &GitRepository) -> Option<String> {
+    repo.find_reference("HEAD")
+        .ok()?
+        .symbolic_target()?
+        .strip_prefix("refs/heads/")
+        .map(|branch| branch.to_string())
+}
+
+fn compare(repo: &GitRepository, base: &str, head: &str) -> Result<Comparison, LocalGitError> {
+    let base_commit = branch_commit(repo, base)?;
+    let head_commit = branch_commit(repo, head)?;
+
+    let ahead = commits_between(repo, base_commit.id(), head_commit.id())?;
+    let behind = commits_between(repo, head_commit.id(), base_commit.id())?;
+    let status = match (ahead.is_empty(), behind.is_empty()) {
+        (true, true) => CompareStatus::Identical,
+        (false, true) => CompareStatus::Ahead,
+        (true, false) => CompareStatus::Behind,
+        (false, false) => CompareStatus::Diverged,
+    };
+
+    let mut commits = vec![];
+    for oid in ahead.iter() {
+        let commit = repo.find_commit(*oid)?;
+        commits.push(Commit {
+            sha: oid.to_string(),
+            message: commit.message().unwrap_or_default().to_string(),
+            author: commit.author().name().map(|name| name.to_string()),
+        });
+    }
+
+    let merge_base = repo.find_commit(repo.merge_base(base_commit.id(), head_commit.id())?)?;
+    let diff =
+        repo.diff_tree_to_tree(Some(&merge_base.tree()?), Some(&base_commit.tree()?), None)?;
+    let files = diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+    Ok(Comparison {
+        status,
+        ahead_by: ahead.len() as u64,
+        behind_by: behind.len() as u64,
+        commits,
+        files,
+    })
+}
+
+fn merge(
+    repo: &GitRepository,
+    base: &str,
+    head: &str,
+    commit_message: &str,
+) -> Result<BranchMerge, LocalGitError> {
+    let base_commit = branch_commit(repo, base)?;
+    let head_commit = branch_commit(repo, head)?;
+    let reference = format!("refs/heads/{}", base);
+
+    let merge_base = repo.merge_base(base_commit.id(), head_commit.id())?;
+    if merge_base == head_commit.id() {
+        return Ok(BranchMerge::NothingToMerge);
+    }
+    if merge_base == base_commit.id() {
+        repo.reference(&reference, head_commit.id(), true, commit_message)?;
+        return Ok(BranchMerge::Merged(head_commit.id().to_string()));
+    }
+
+    let mut index = repo.merge_commits(&base_commit, &head_commit, None)?;
+    if index.has_conflicts() {
+        return Ok(BranchMerge::Conflict);
+    }
+
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+    let signature = match repo.signature() {
+        Ok(signature) => signature,
+        Err(_) => Signature::now("multigit", "multigit@localhost")?,
+    };
+    let oid = repo.commit(
+        Some(&reference),
+        &signature,
+        &signature,
+        commit_message,
+        &tree,
+        &[&base_commit, &head_commit],
+    )?;
+    Ok(BranchMerge::Merged(oid.to_string()))
+}
+
+impl LocalGit {
+    /// Runs `f` on the repository `name` on the blocking pool, so that a slow
+    /// repo holds neither the async workers nor the jobs of the other repos.
+    async fn with_repo<T, F>(&self, name: &str, f: F) -> Result<T, ForgeError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GitRepository) -> Result<T, LocalGitError> + Send + 'static,
+    {
+        let local_git = self.clone();
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || f(&local_git.open(&name)?))
+            .await
+            .map_err(|e| ForgeError::new(format!("git task failed: {}", e)))?
+            .map_err(ForgeError::from)
+    }
+}
+
+/// [`Forge`] over the bare repositories of [`LocalGit::root`], without pull
+/// requests: promotions go through direct merges.
+#[async_trait]
+impl Forge for LocalGit {
+    async fn get_repo(&self, name: &str) -> Result<Repository, ForgeError> {
+        let name = name.to_string();
+        self.with_repo(&name.clone(), move |repo| {
+            let path = repo.path().to_string_lossy().to_string();
+            Ok(Repository {
+                full_name: name.clone(),
+                name,
+                web_url: path.clone(),
+                clone_url: path,
+                default_branch: default_branch(repo),
+                archived: false,
+            })
+        })
+        .await
+    }
+
+    async fn list_branches(&self, repo: &Repository) -> Result<Vec<Branch>, ForgeError> {
+        self.with_repo(&repo.name, list_branches).await
+    }
+
+    async fn compare_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+    ) -> Result<Comparison, ForgeError> {
+        let (base, head) = (base.to_string(), head.to_string());
+        self.with_repo(&repo.name, move |repo| compare(repo, &base, &head))
+            .await
+    }
+
+    async fn create_branch(
+        &self,
+        repo: &Repository,
+        branch: &str,
+        from: &str,
+    ) -> Result<(), ForgeError> {
+        let (branch, from) = (branch.to_string(), from.to_string());
+        self.with_repo(&repo.name, move |git_repo| {
+            let from_commit = branch_commit(git_repo, &from)?;
+            git_repo.branch(&branch, &from_commit, false)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete_branch(&self, repo: &Repository, branch: &str) -> Result<(), ForgeError> {
+        let reference = format!("refs/heads/{}", branch);
+        self.with_repo(&repo.name, move |git_repo| {
+            git_repo.find_reference(&reference)?.delete()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn merge_branches(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+        commit_message: &str,
+    ) -> Result<BranchMerge, ForgeError> {
+        let (base, head) = (base.to_string(), head.to_string());
+        let commit_message = commit_message.to_string();
+        self.with_repo(&repo.name, move |repo| {
+            merge(repo, &base, &head, &commit_message)
+        })
+        .await
+    }
+}
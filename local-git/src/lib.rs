@@ -0,0 +1,98 @@
+mod forge;
+mod merge;
+
+pub use merge::MergePrediction;
+
+use std::path::PathBuf;
+
+use git2::{Cred, FetchOptions, RemoteCallbacks, Repository};
+use log::debug;
+
+#[derive(Debug)]
+pub struct LocalGitError {
+    pub message: String,
+}
+
+impl LocalGitError {
+    pub fn error_message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<git2::Error> for LocalGitError {
+    fn from(e: git2::Error) -> Self {
+        LocalGitError {
+            message: e.message().to_string(),
+        }
+    }
+}
+
+impl From<std::io::Error> for LocalGitError {
+    fn from(e: std::io::Error) -> Self {
+        LocalGitError {
+            message: e.to_string(),
+        }
+    }
+}
+
+/// A directory of bare repositories, one `<name>.git` per repo, whose
+/// branches live under `refs/heads` like on the forge.
+#[derive(Clone)]
+pub struct LocalGit {
+    pub root: PathBuf,
+}
+
+impl LocalGit {
+    pub fn new(root: PathBuf) -> LocalGit {
+        LocalGit { root }
+    }
+
+    fn repo_path(&self, name: &str) -> PathBuf {
+        self.root.join(format!("{}.git", name))
+    }
+
+    pub fn open(&self, name: &str) -> Result<Repository, LocalGitError> {
+        Ok(Repository::open_bare(self.repo_path(name))?)
+    }
+
+    /// Mirrors `branches` of the repository at `url` into `<root>/<name>.git`,
+    /// creating the bare repository on first use. `name` may be
+    /// `<owner>/<name>` to keep same-named repos of several owners apart.
+    pub fn fetch(
+        &self,
+        name: &str,
+        url: &str,
+        branches: &[&String],
+        token: Option<&String>,
+    ) -> Result<Repository, LocalGitError> {
+        let path = self.repo_path(name);
+        let repo = if path.exists() {
+            Repository::open_bare(&path)?
+        } else {
+            std::fs::create_dir_all(&path)?;
+            Repository::init_bare(&path)?
+        };
+
+        {
+            let mut remote = repo.remote_anonymous(url)?;
+            let refspecs: Vec<String> = branches
+                .iter()
+                .map(|branch| format!("+refs/heads/{0}:refs/heads/{0}", branch))
+                .collect();
+
+            let mut callbacks = RemoteCallbacks::new();
+            if let Some(token) = token {
+                let token = token.clone();
+                callbacks
+                    .credentials(move |_, _, _| Cred::userpass_plaintext("x-access-token", &token));
+            }
+            let mut options = FetchOptions::new();
+            options.remote_callbacks(callbacks);
+
+            debug!("fetching {:?} from {} into {:?}", refspecs, url, path);
+            remote.fetch(&refspecs, Some(&mut options), None)?;
+        }
+
+        Ok(repo)
+    }
+}
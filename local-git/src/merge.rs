@@ -0,0 +1,62 @@
+use git2::{Commit, Repository};
+
+use crate::{LocalGit, LocalGitError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MergePrediction {
+    /// `to` already contains `from`
+    UpToDate,
+    /// `to` can be fast-forwarded to `from`
+    FastForward,
+    /// a merge commit is needed and would apply cleanly
+    Clean,
+    /// the merge would conflict on these files
+    Conflict(Vec<String>),
+}
+
+pub(crate) fn branch_commit<'r>(
+    repo: &'r Repository,
+    branch: &str,
+) -> Result<Commit<'r>, LocalGitError> {
+    let reference = repo.find_reference(&format!("refs/heads/{}", branch))?;
+    Ok(reference.peel_to_commit()?)
+}
+
+impl LocalGit {
+    /// Performs an in-memory three-way merge of `from` into `to`, nothing is
+    /// written to the repository.
+    pub fn predict_merge(
+        &self,
+        name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<MergePrediction, LocalGitError> {
+        let repo = self.open(name)?;
+        let from_commit = branch_commit(&repo, from)?;
+        let to_commit = branch_commit(&repo, to)?;
+
+        let merge_base = repo.merge_base(from_commit.id(), to_commit.id())?;
+        if merge_base == from_commit.id() {
+            return Ok(MergePrediction::UpToDate);
+        }
+        if merge_base == to_commit.id() {
+            return Ok(MergePrediction::FastForward);
+        }
+
+        let index = repo.merge_commits(&to_commit, &from_commit, None)?;
+        if !index.has_conflicts() {
+            return Ok(MergePrediction::Clean);
+        }
+
+        let mut files: Vec<String> = vec![];
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                files.push(String::from_utf8_lossy(&entry.path).to_string());
+            }
+        }
+        files.sort();
+        files.dedup();
+        Ok(MergePrediction::Conflict(files))
+    }
+}
@@ -0,0 +1,119 @@
+use git2::{Oid, Repository, Signature};
+use local_git::{LocalGit, MergePrediction};
+use tempfile::TempDir;
+
+fn commit_files(
+    repo: &Repository,
+    branch: &str,
+    parent: Option<Oid>,
+    files: &[(&str, &str)],
+) -> Oid {
+    let parent = parent.map(|oid| repo.find_commit(oid).unwrap());
+    let mut tree_builder = repo
+        .treebuilder(parent.as_ref().map(|c| c.tree().unwrap()).as_ref())
+        .unwrap();
+    for (path, content) in files {
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        tree_builder.insert(path, blob, 0o100644).unwrap();
+    }
+    let tree = repo.find_tree(tree_builder.write().unwrap()).unwrap();
+    let signature = Signature::now("multigit", "multigit@example.com").unwrap();
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(
+        Some(&format!("refs/heads/{}", branch)),
+        &signature,
+        &signature,
+        "commit",
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
+
+/// upstream repo where `main` and `prod` share a base commit
+fn upstream() -> (TempDir, Repository, Oid) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init_bare(dir.path()).unwrap();
+    let base = commit_files(
+        &repo,
+        "main",
+        None,
+        &[("README.md", "hello\n"), ("lib.rs", "")],
+    );
+    repo.branch("prod", &repo.find_commit(base).unwrap(), false)
+        .unwrap();
+    (dir, repo, base)
+}
+
+fn predict(upstream_dir: &TempDir) -> MergePrediction {
+    let cache = TempDir::new().unwrap();
+    let local = LocalGit::new(cache.path().to_path_buf());
+    let (from, to) = (String::from("main"), String::from("prod"));
+    local
+        .fetch(
+            "repo",
+            upstream_dir.path().to_str().unwrap(),
+            &[&from, &to],
+            None,
+        )
+        .unwrap();
+    local.predict_merge("repo", &from, &to).unwrap()
+}
+
+#[test]
+fn identical_branches_are_up_to_date() {
+    let (dir, _repo, _base) = upstream();
+    assert_eq!(predict(&dir), MergePrediction::UpToDate);
+}
+
+#[test]
+fn behind_branch_fast_forwards() {
+    let (dir, repo, base) = upstream();
+    commit_files(&repo, "main", Some(base), &[("lib.rs", "fn main() {}\n")]);
+    assert_eq!(predict(&dir), MergePrediction::FastForward);
+}
+
+#[test]
+fn changes_on_different_files_merge_cleanly() {
+    let (dir, repo, base) = upstream();
+    commit_files(&repo, "main", Some(base), &[("lib.rs", "fn main() {}\n")]);
+    commit_files(&repo, "prod", Some(base), &[("README.md", "hotfix\n")]);
+    assert_eq!(predict(&dir), MergePrediction::Clean);
+}
+
+#[test]
+fn changes_on_same_file_conflict() {
+    let (dir, repo, base) = upstream();
+    commit_files(&repo, "main", Some(base), &[("README.md", "feature\n")]);
+    commit_files(&repo, "prod", Some(base), &[("README.md", "hotfix\n")]);
+    assert_eq!(
+        predict(&dir),
+        MergePrediction::Conflict(vec![String::from("README.md")])
+    );
+}
+
+#[test]
+fn same_named_repos_of_several_owners_are_cached_apart() {
+    let (up_to_date, _repo, _base) = upstream();
+    let (behind, repo, base) = upstream();
+    commit_files(&repo, "main", Some(base), &[("lib.rs", "fn main() {}\n")]);
+
+    let cache = TempDir::new().unwrap();
+    let local = LocalGit::new(cache.path().to_path_buf());
+    let (from, to) = (String::from("main"), String::from("prod"));
+    for (name, dir) in [("acme/repo", &up_to_date), ("rednaks/repo", &behind)] {
+        local
+            .fetch(name, dir.path().to_str().unwrap(), &[&from, &to], None)
+            .unwrap();
+    }
+
+    assert_eq!(
+        local.predict_merge("acme/repo", &from, &to).unwrap(),
+        MergePrediction::UpToDate
+    );
+    assert_eq!(
+        local.predict_merge("rednaks/repo", &from, &to).unwrap(),
+        MergePrediction::FastForward
+    );
+    assert!(cache.path().join("rednaks/repo.git").exists());
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use actix_web::{test, web, App};
+use fake_github::{FakeGithub, Fixture};
+use github::Github;
+use web_apis::AppState;
+use web_common::{OrgResponse, OrgType, RepoResponse};
+
+fn start_fake() -> FakeGithub {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../fake-github/fixtures/promotion.json");
+    FakeGithub::start(Fixture::load(&fixture).unwrap())
+}
+
+fn app_state(fake: &FakeGithub) -> web::Data<AppState> {
+    let gh = Github::new(String::from("fake-token"), String::from("rednaks"))
+        .with_api_url(fake.url().to_string());
+    web::Data::new(AppState { gh })
+}
+
+#[actix_web::test]
+async fn orgs_lists_the_user_first() {
+    let fake = start_fake();
+    let app = test::init_service(
+        App::new()
+            .app_data(app_state(&fake))
+            .configure(web_apis::configure),
+    )
+    .await;
+
+    let request = test::TestRequest::get().uri("/api/orgs").to_request();
+    let orgs: Vec<OrgResponse> = test::call_and_read_body_json(&app, request).await;
+
+    assert_eq!(
+        orgs,
+        vec![
+            OrgResponse {
+                login: String::from("octocat"),
+                org_type: OrgType::User,
+            },
+            OrgResponse {
+                login: String::from("rednaks"),
+                org_type: OrgType::Organization,
+            },
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn org_repos_are_listed() {
+    let fake = start_fake();
+    let app = test::init_service(
+        App::new()
+            .app_data(app_state(&fake))
+            .configure(web_apis::configure),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/api/orgs/rednaks/repos?type=organization")
+        .to_request();
+    let repos: Vec<RepoResponse> = test::call_and_read_body_json(&app, request).await;
+
+    let names: Vec<String> = repos.into_iter().map(|repo| repo.name).collect();
+    assert_eq!(names, vec!["api", "api-fork", "legacy", "web"]);
+}
+
+#[actix_web::test]
+async fn unknown_orgs_fall_back_to_the_user() {
+    let fake = start_fake();
+    let app = test::init_service(
+        App::new()
+            .app_data(app_state(&fake))
+            .configure(web_apis::configure),
+    )
+    .await;
+
+    let request = test::TestRequest::get()
+        .uri("/api/orgs/rednaks")
+        .to_request();
+    let org: OrgResponse = test::call_and_read_body_json(&app, request).await;
+    assert_eq!(org.org_type, OrgType::Organization);
+
+    let request = test::TestRequest::get()
+        .uri("/api/orgs/octocat")
+        .to_request();
+    let user: OrgResponse = test::call_and_read_body_json(&app, request).await;
+    assert_eq!(user.login, "octocat");
+    assert_eq!(user.org_type, OrgType::User);
+}